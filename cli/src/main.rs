@@ -118,7 +118,7 @@ fn main() -> Result<()> {
             EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
         println!(
             "🔧 Effective log level from RUST_LOG or default: {}",
-            env_filter.to_string()
+            env_filter
         );
         env_filter
     };
@@ -447,8 +447,8 @@ fn run_lint_checks(mode: &LintMode, dry_run: bool) -> Result<()> {
 
     let run_checks_file = "run-checks.sh";
     let script = body.join("\n") + "\n";
-    write(&run_checks_file, script)?;
-    std::fs::set_permissions(&run_checks_file, std::fs::Permissions::from_mode(0o755))?;
+    write(run_checks_file, script)?;
+    std::fs::set_permissions(run_checks_file, std::fs::Permissions::from_mode(0o755))?;
 
     // `setup_env_for_compose` already called so will not fail.
     let rust_dev_image = get_env_no_default("RUST_DEV_IMAGE")?;
@@ -0,0 +1,211 @@
+use std::cell::Cell;
+use std::future::Future;
+use std::rc::Rc;
+
+use gloo_net::http::{Method, Request, Response};
+use gloo_timers::callback::Timeout;
+use gloo_timers::future::TimeoutFuture;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use web_sys::{AbortController, AbortSignal};
+use yew::Callback;
+
+use super::error::{expect_success, parse_json, ApiError};
+
+/// Requests still pending after this long are aborted and reported as network errors.
+const REQUEST_TIMEOUT_MS: u32 = 15_000;
+
+/// Extra attempts for idempotent requests that failed at the network level or hit
+/// a gateway error; the delay grows linearly with each attempt.
+const GET_RETRIES: u32 = 2;
+const RETRY_BACKOFF_MS: u32 = 300;
+
+thread_local! {
+    static NEXT_REQUEST_ID: Cell<u64> = const { Cell::new(1) };
+}
+
+/// HTTP client for the cr8s backend. Owns the API base URL and, once logged in,
+/// the session token, which is attached to every request as a bearer token.
+///
+/// Every request also carries `Accept: application/json` and an `X-Request-Id`
+/// for matching it with the backend logs. The latter is not a CORS-safelisted
+/// header: when the API lives on another origin, requests are preflighted and
+/// the backend must allow `X-Request-Id` in `Access-Control-Allow-Headers`.
+///
+/// Cheap to clone; components get one from `CurrentUser::client()`.
+#[derive(Clone, PartialEq)]
+pub struct ApiClient {
+    base: Rc<str>,
+    token: Option<Rc<str>>,
+    on_unauthorized: Option<Callback<()>>,
+}
+
+impl ApiClient {
+    pub fn new(base: &str) -> Self {
+        Self {
+            base: base.trim_end_matches('/').into(),
+            token: None,
+            on_unauthorized: None,
+        }
+    }
+
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    pub fn with_token(&self, token: &str) -> Self {
+        Self {
+            token: Some(token.into()),
+            ..self.clone()
+        }
+    }
+
+    /// Invoked whenever a request that carried a token is answered with 401, i.e.
+    /// the session expired or was revoked behind our back.
+    pub fn on_unauthorized(self, callback: Callback<()>) -> Self {
+        Self {
+            on_unauthorized: Some(callback),
+            ..self
+        }
+    }
+
+    pub async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, ApiError> {
+        self.send(Method::GET, path, None::<&()>, parse_json::<T>)
+            .await
+    }
+
+    pub async fn post<B, T>(&self, path: &str, body: &B) -> Result<T, ApiError>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        self.send(Method::POST, path, Some(body), parse_json::<T>)
+            .await
+    }
+
+    /// POST without a body, for endpoints whose answer carries nothing we need.
+    pub async fn post_empty(&self, path: &str) -> Result<(), ApiError> {
+        self.send(Method::POST, path, None::<&()>, expect_success)
+            .await
+    }
+
+    pub async fn put<B, T>(&self, path: &str, body: &B) -> Result<T, ApiError>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        self.send(Method::PUT, path, Some(body), parse_json::<T>)
+            .await
+    }
+
+    pub async fn delete(&self, path: &str) -> Result<(), ApiError> {
+        self.send(Method::DELETE, path, None::<&()>, expect_success)
+            .await
+    }
+
+    /// The single choke point for every request: default headers, auth, timeout
+    /// and retries all live here. `read` consumes the response while the timeout
+    /// still runs, so a body that stalls after the headers is aborted as well.
+    async fn send<B, T, F, Fut>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&B>,
+        read: F,
+    ) -> Result<T, ApiError>
+    where
+        B: Serialize + ?Sized,
+        F: FnOnce(Response) -> Fut,
+        Fut: Future<Output = Result<T, ApiError>>,
+    {
+        // ---
+        let retries = match method {
+            Method::GET => GET_RETRIES,
+            _ => 0,
+        };
+
+        let mut attempt = 0;
+        loop {
+            // The timer aborts the fetch unless it is dropped (cancelled) first.
+            let controller = AbortController::new().ok();
+            let signal = controller.as_ref().map(AbortController::signal);
+            let _timeout = controller
+                .map(|controller| Timeout::new(REQUEST_TIMEOUT_MS, move || controller.abort()));
+            let timed_out = || signal.as_ref().is_some_and(AbortSignal::aborted);
+
+            let result = self.send_once(method, path, body, signal.as_ref()).await;
+            let retryable = match &result {
+                Err(ApiError::Network(_)) => true,
+                Ok(response) => matches!(response.status(), 502..=504),
+                Err(_) => false,
+            };
+            if retryable && attempt < retries {
+                attempt += 1;
+                log::debug!("{method} {path} failed, retry {attempt}/{retries}");
+                TimeoutFuture::new(RETRY_BACKOFF_MS * attempt).await;
+                continue;
+            }
+
+            let response = match result {
+                Err(ApiError::Network(_)) if timed_out() => return Err(timeout_error()),
+                result => result?,
+            };
+            if let (401, Some(_), Some(callback)) =
+                (response.status(), &self.token, &self.on_unauthorized)
+            {
+                callback.emit(());
+            }
+            return match read(response).await {
+                Err(ApiError::Network(_)) if timed_out() => Err(timeout_error()),
+                result => result,
+            };
+        }
+    }
+
+    async fn send_once<B>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&B>,
+        signal: Option<&AbortSignal>,
+    ) -> Result<Response, ApiError>
+    where
+        B: Serialize + ?Sized,
+    {
+        // ---
+        let mut request = Request::new(&format!("{}{}", self.base, path))
+            .method(method)
+            .header("Accept", "application/json")
+            .header("X-Request-Id", &next_request_id());
+        if let Some(token) = &self.token {
+            request = request.header("Authorization", &format!("Bearer {token}"));
+        }
+        if let Some(body) = body {
+            request = request.json(body)?;
+        }
+
+        request
+            .abort_signal(signal)
+            .send()
+            .await
+            .map_err(|e| ApiError::Network(e.to_string()))
+    }
+}
+
+/// E.g. `cr8s-fe-18f3a2b4c1d-7`. Retries get a new one, so each attempt can be
+/// told apart in the backend logs.
+fn next_request_id() -> String {
+    let seq = NEXT_REQUEST_ID.with(|id| {
+        let seq = id.get();
+        id.set(seq + 1);
+        seq
+    });
+    format!("cr8s-fe-{:x}-{seq}", js_sys::Date::now() as u64)
+}
+
+fn timeout_error() -> ApiError {
+    ApiError::Network(format!(
+        "request timed out after {}s",
+        REQUEST_TIMEOUT_MS / 1000
+    ))
+}
@@ -1,9 +1,9 @@
-use gloo_net::http::Request;
-use gloo_net::Error;
 use serde::Deserialize;
 use serde_json::json;
 
-use super::app_base;
+use super::client::ApiClient;
+use super::error::ApiError;
+use super::page::{Page, PageBody, PageQuery};
 
 #[derive(Deserialize, Clone, PartialEq)]
 pub struct Crate {
@@ -16,77 +16,69 @@ pub struct Crate {
     pub created_at: String,
 }
 
-pub async fn api_crate_show(token: &String, id: i32) -> Result<Crate, Error> {
-    let response = Request::get(&format!("{}/crates/{}", &app_base(), id))
-        .header("Authorization", &format!("Bearer {}", token))
-        .send()
-        .await?;
+pub async fn api_crate_show(client: &ApiClient, id: i32) -> Result<Crate, ApiError> {
+    client.get(&format!("/crates/{}", id)).await
+}
 
-    response.json::<Crate>().await
+pub async fn api_crates(client: &ApiClient) -> Result<Vec<Crate>, ApiError> {
+    client.get("/crates").await
 }
 
-pub async fn api_crates(token: &String) -> Result<Vec<Crate>, Error> {
-    let response = Request::get(&format!("{}/crates", &app_base()))
-        .header("Authorization", &format!("Bearer {}", token))
-        .send()
+pub async fn api_crates_page(
+    client: &ApiClient,
+    query: PageQuery,
+) -> Result<Page<Crate>, ApiError> {
+    let body: PageBody<Crate> = client
+        .get(&format!("/crates?{}", query.to_query_string()))
         .await?;
-
-    response.json::<Vec<Crate>>().await
+    Ok(body.into_page(query))
 }
 
 pub async fn api_crate_create(
-    token: &String,
+    client: &ApiClient,
     name: String,
     code: String,
     rustacean_id: i32,
     version: String,
     description: String,
-) -> Result<Crate, Error> {
-    let response = Request::post(&format!("{}/crates", &app_base()))
-        .header("Authorization", &format!("Bearer {}", token))
-        .header("Accept", "application/json")
-        .json(&json!({
-            "name": name,
-            "code": code,
-            "rustacean_id": rustacean_id,
-            "version": version,
-            "description": description
-        }))?
-        .send()
-        .await?;
-
-    response.json::<Crate>().await
+) -> Result<Crate, ApiError> {
+    client
+        .post(
+            "/crates",
+            &json!({
+                "name": name,
+                "code": code,
+                "rustacean_id": rustacean_id,
+                "version": version,
+                "description": description
+            }),
+        )
+        .await
 }
 
 pub async fn api_crate_update(
-    token: &String,
+    client: &ApiClient,
     id: i32,
     name: String,
     code: String,
     rustacean_id: i32,
     version: String,
     description: String,
-) -> Result<Crate, Error> {
-    let response = Request::put(&format!("{}/crates/{}", &app_base(), id))
-        .header("Authorization", &format!("Bearer {}", token))
-        .json(&json!({
-            "name": name,
-            "code": code,
-            "rustacean_id": rustacean_id,
-            "version": version,
-            "description": description
-        }))?
-        .send()
-        .await?;
-
-    response.json::<Crate>().await
+) -> Result<Crate, ApiError> {
+    client
+        .put(
+            &format!("/crates/{}", id),
+            &json!({
+                "name": name,
+                "code": code,
+                "rustacean_id": rustacean_id,
+                "version": version,
+                "description": description
+            }),
+        )
+        .await
 }
 
-pub async fn api_crate_delete(token: &String, id: i32) -> Result<(), Error> {
-    let _ = Request::delete(&format!("{}/crates/{}", &app_base(), id))
-        .header("Authorization", &format!("Bearer {}", token))
-        .send()
-        .await?;
-
-    Ok(())
+pub async fn api_crate_delete(client: &ApiClient, id: i32) -> Result<(), ApiError> {
+    client.delete(&format!("/crates/{}", id)).await
 }
@@ -0,0 +1,256 @@
+use std::fmt;
+
+use gloo_net::http::Response;
+use indexmap::IndexMap;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Field name -> messages, in the order the backend reported them.
+pub type FieldErrors = IndexMap<String, Vec<String>>;
+
+/// Every failure an `api_*` call can report. Non-2xx responses are classified by
+/// status code and their body is parsed for a message and per-field errors.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ApiError {
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    Validation {
+        message: Option<String>,
+        fields: FieldErrors,
+    },
+    Conflict(Option<String>),
+    Server {
+        status: u16,
+        message: Option<String>,
+    },
+    /// The request never produced a response (offline, CORS, DNS, ...).
+    Network(String),
+    /// A 2xx response whose body did not match the expected type.
+    Decode(String),
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::Unauthorized => write!(f, "Session expired. Please login again"),
+            ApiError::Forbidden => write!(f, "You are not allowed to perform this action"),
+            ApiError::NotFound => write!(f, "The requested record does not exist"),
+            ApiError::Validation { message, fields } => {
+                write!(f, "{}", message.as_deref().unwrap_or("Validation failed"))?;
+                for (field, messages) in fields {
+                    write!(f, "; {field}: {}", messages.join(", "))?;
+                }
+                Ok(())
+            }
+            ApiError::Conflict(message) => write!(
+                f,
+                "{}",
+                message
+                    .as_deref()
+                    .unwrap_or("The record conflicts with an existing one")
+            ),
+            ApiError::Server { status, message } => match message {
+                Some(message) => write!(f, "Server error ({status}): {message}"),
+                None => write!(f, "Server error ({status})"),
+            },
+            ApiError::Network(e) => write!(f, "Network error: {e}"),
+            ApiError::Decode(e) => write!(f, "Unexpected response from server: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl From<gloo_net::Error> for ApiError {
+    fn from(e: gloo_net::Error) -> Self {
+        match e {
+            gloo_net::Error::SerdeError(e) => ApiError::Decode(e.to_string()),
+            e => ApiError::Network(e.to_string()),
+        }
+    }
+}
+
+impl ApiError {
+    /// Build the error for a non-2xx response, reading whatever body it carries.
+    pub async fn from_response(response: Response) -> Self {
+        // ---
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .ok()
+            .and_then(|text| serde_json::from_str::<Value>(&text).ok());
+        let message = body.as_ref().and_then(body_message);
+
+        match status {
+            401 => ApiError::Unauthorized,
+            403 => ApiError::Forbidden,
+            404 => ApiError::NotFound,
+            409 => ApiError::Conflict(message),
+            400 | 422 => ApiError::Validation {
+                message,
+                fields: body.as_ref().map(body_field_errors).unwrap_or_default(),
+            },
+            status => ApiError::Server { status, message },
+        }
+    }
+}
+
+/// Decode a successful response as `T`, or turn a failed one into an `ApiError`.
+pub async fn parse_json<T: DeserializeOwned>(response: Response) -> Result<T, ApiError> {
+    if !response.ok() {
+        return Err(ApiError::from_response(response).await);
+    }
+    response.json::<T>().await.map_err(ApiError::from)
+}
+
+/// Like `parse_json` for endpoints whose success body is irrelevant (e.g. DELETE).
+pub async fn expect_success(response: Response) -> Result<(), ApiError> {
+    if !response.ok() {
+        return Err(ApiError::from_response(response).await);
+    }
+    Ok(())
+}
+
+const MESSAGE_KEYS: [&str; 3] = ["message", "error", "detail"];
+
+/// Keys of a bare error object that describe the error rather than a field.
+const NON_FIELD_KEYS: [&str; 7] = [
+    "code", "status", "type", "title", "instance", "errors", "fields",
+];
+
+fn body_message(body: &Value) -> Option<String> {
+    if let Value::String(s) = body {
+        return Some(s.clone());
+    }
+    MESSAGE_KEYS.iter().find_map(|key| match body.get(key) {
+        Some(Value::String(s)) => Some(s.clone()),
+        Some(nested @ Value::Object(_)) => body_message(nested),
+        _ => None,
+    })
+}
+
+/// Accepts `{"errors": {...}}`, `{"fields": {...}}` or a bare object (minus its
+/// message and `NON_FIELD_KEYS`), where each field maps to a string, a list of strings, or a list of `{"message": ...}`.
+fn body_field_errors(body: &Value) -> FieldErrors {
+    // ---
+    let (map, bare) = match body.get("errors").or_else(|| body.get("fields")) {
+        Some(Value::Object(map)) => (map, false),
+        _ => match body {
+            Value::Object(map) => (map, true),
+            _ => return FieldErrors::default(),
+        },
+    };
+
+    let mut fields = FieldErrors::new();
+    for (field, value) in map {
+        let key = field.as_str();
+        if bare && (MESSAGE_KEYS.contains(&key) || NON_FIELD_KEYS.contains(&key)) {
+            continue;
+        }
+        let messages: Vec<String> = match value {
+            Value::String(s) => vec![s.clone()],
+            Value::Array(items) => items.iter().filter_map(item_message).collect(),
+            Value::Object(_) => item_message(value).into_iter().collect(),
+            _ => vec![],
+        };
+        if !messages.is_empty() {
+            fields.insert(field.clone(), messages);
+        }
+    }
+    fields
+}
+
+fn item_message(item: &Value) -> Option<String> {
+    match item {
+        Value::String(s) => Some(s.clone()),
+        Value::Object(_) => match item.get("message") {
+            Some(Value::String(s)) => Some(s.clone()),
+            _ => item.get("code").and_then(Value::as_str).map(str::to_string),
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fields(pairs: &[(&str, &[&str])]) -> FieldErrors {
+        pairs
+            .iter()
+            .map(|(field, messages)| {
+                let messages = messages.iter().map(|m| m.to_string()).collect();
+                (field.to_string(), messages)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn message_from_string_or_known_keys() {
+        assert_eq!(body_message(&json!("Nope")).as_deref(), Some("Nope"));
+        assert_eq!(
+            body_message(&json!({"message": "Bad"})).as_deref(),
+            Some("Bad")
+        );
+        assert_eq!(
+            body_message(&json!({"detail": "Gone"})).as_deref(),
+            Some("Gone")
+        );
+        let nested = json!({"error": {"code": 422, "message": "Invalid"}});
+        assert_eq!(body_message(&nested).as_deref(), Some("Invalid"));
+        assert_eq!(body_message(&json!({"code": "invalid"})), None);
+    }
+
+    #[test]
+    fn field_errors_under_errors() {
+        let body = json!({
+            "message": "Validation failed",
+            "errors": {
+                "code": ["too long", "taken"],
+                "name": "required",
+                "rustacean_id": [{"message": "unknown author"}, {"code": "range"}],
+            }
+        });
+        assert_eq!(
+            body_field_errors(&body),
+            fields(&[
+                ("code", &["too long", "taken"]),
+                ("name", &["required"]),
+                ("rustacean_id", &["unknown author", "range"]),
+            ])
+        );
+    }
+
+    #[test]
+    fn field_errors_under_fields() {
+        let body = json!({"fields": {"email": {"message": "invalid email"}}});
+        assert_eq!(
+            body_field_errors(&body),
+            fields(&[("email", &["invalid email"])])
+        );
+    }
+
+    #[test]
+    fn bare_object_skips_non_field_keys() {
+        let body = json!({
+            "message": "Invalid input",
+            "code": "invalid",
+            "status": 422,
+            "name": ["must not be empty"],
+        });
+        assert_eq!(
+            body_field_errors(&body),
+            fields(&[("name", &["must not be empty"])])
+        );
+    }
+
+    #[test]
+    fn no_field_errors_without_an_object() {
+        assert!(body_field_errors(&json!("Bad request")).is_empty());
+        assert!(body_field_errors(&json!(["a", "b"])).is_empty());
+        assert!(body_field_errors(&json!({"errors": "nope"})).is_empty());
+    }
+}
@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_PAGE_SIZE: u32 = 25;
+pub const PAGE_SIZES: [u32; 4] = [10, 25, 50, 100];
+
+/// Far beyond any real list; keeps page arithmetic clear of overflow.
+pub const MAX_PAGE: u32 = 1_000_000;
+
+/// `?page=&limit=` as sent to the backend and reflected in the app's own URL.
+/// Pages are 1-based.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub struct PageQuery {
+    #[serde(default = "first_page")]
+    pub page: u32,
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+}
+
+impl Default for PageQuery {
+    fn default() -> Self {
+        Self {
+            page: first_page(),
+            limit: default_limit(),
+        }
+    }
+}
+
+impl PageQuery {
+    /// Clamp values coming from a hand-edited URL.
+    pub fn normalized(self) -> Self {
+        Self {
+            page: self.page.clamp(1, MAX_PAGE),
+            limit: self
+                .limit
+                .clamp(1, *PAGE_SIZES.last().unwrap_or(&DEFAULT_PAGE_SIZE)),
+        }
+    }
+
+    /// Rows before this page. `u64`, as `usize` is only 32 bits on wasm32.
+    pub fn offset(&self) -> u64 {
+        u64::from(self.page.saturating_sub(1)).saturating_mul(u64::from(self.limit))
+    }
+
+    pub fn to_query_string(self) -> String {
+        format!("page={}&limit={}", self.page, self.limit)
+    }
+}
+
+fn first_page() -> u32 {
+    1
+}
+
+fn default_limit() -> u32 {
+    DEFAULT_PAGE_SIZE
+}
+
+/// One page of a list plus, when the backend reports it, the total row count.
+#[derive(Clone, PartialEq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: Option<u64>,
+    pub query: PageQuery,
+}
+
+impl<T> Page<T> {
+    /// Without a total, a full page is the only hint that more rows follow.
+    pub fn has_next(&self) -> bool {
+        match self.total {
+            Some(total) => self.query.offset().saturating_add(self.items.len() as u64) < total,
+            None => self.items.len() >= self.query.limit as usize,
+        }
+    }
+}
+
+/// Accepts `{"items": [...], "total": n}` (or `data` instead of `items`) and,
+/// for backends without pagination support, a bare array.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(super) enum PageBody<T> {
+    Envelope {
+        #[serde(alias = "data")]
+        items: Vec<T>,
+        #[serde(default)]
+        total: Option<u64>,
+    },
+    Bare(Vec<T>),
+}
+
+impl<T> PageBody<T> {
+    pub(super) fn into_page(self, query: PageQuery) -> Page<T> {
+        match self {
+            PageBody::Envelope { items, total } => Page {
+                items,
+                total,
+                query,
+            },
+            // A bare array means the backend knows nothing of `page`/`limit` and
+            // sent every row, so paginate locally.
+            PageBody::Bare(items) => {
+                let total = items.len() as u64;
+                let items = items
+                    .into_iter()
+                    .skip(usize::try_from(query.offset()).unwrap_or(usize::MAX))
+                    .take(query.limit as usize)
+                    .collect();
+                Page {
+                    items,
+                    total: Some(total),
+                    query,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalized_clamps_hand_edited_values() {
+        let query = PageQuery { page: 0, limit: 0 }.normalized();
+        assert_eq!(query, PageQuery { page: 1, limit: 1 });
+        let query = PageQuery {
+            page: u32::MAX,
+            limit: u32::MAX,
+        }
+        .normalized();
+        assert_eq!(
+            query,
+            PageQuery {
+                page: MAX_PAGE,
+                limit: 100
+            }
+        );
+    }
+
+    #[test]
+    fn offset_does_not_overflow() {
+        assert_eq!(PageQuery { page: 1, limit: 25 }.offset(), 0);
+        assert_eq!(PageQuery { page: 3, limit: 25 }.offset(), 50);
+        let query = PageQuery {
+            page: u32::MAX,
+            limit: u32::MAX,
+        };
+        assert_eq!(
+            query.offset(),
+            u64::from(u32::MAX - 1) * u64::from(u32::MAX)
+        );
+    }
+
+    #[test]
+    fn bare_arrays_are_paginated_locally() {
+        let rows: Vec<u32> = (1..=30).collect();
+        let page = PageBody::Bare(rows.clone()).into_page(PageQuery { page: 2, limit: 25 });
+        assert_eq!(page.items, (26..=30).collect::<Vec<_>>());
+        assert_eq!(page.total, Some(30));
+        assert!(!page.has_next());
+
+        let page = PageBody::Bare(rows).into_page(PageQuery {
+            page: MAX_PAGE,
+            limit: 100,
+        });
+        assert!(page.items.is_empty());
+        assert!(!page.has_next());
+    }
+}
@@ -1,9 +1,9 @@
-use gloo_net::http::Request;
-use gloo_net::Error;
 use serde::Deserialize;
 use serde_json::json;
 
-use super::app_base;
+use super::client::ApiClient;
+use super::error::ApiError;
+use super::page::{Page, PageBody, PageQuery};
 
 #[derive(Deserialize, Clone, PartialEq)]
 pub struct Rustacean {
@@ -13,64 +13,57 @@ pub struct Rustacean {
     pub created_at: String,
 }
 
-pub async fn api_rustaceans(token: &String) -> Result<Vec<Rustacean>, Error> {
-    let response = Request::get(&format!("{}/rustaceans", &app_base()))
-        .header("Authorization", &format!("Bearer {}", token))
-        .send()
-        .await?;
-
-    response.json::<Vec<Rustacean>>().await
+pub async fn api_rustaceans(client: &ApiClient) -> Result<Vec<Rustacean>, ApiError> {
+    client.get("/rustaceans").await
 }
 
-pub async fn api_rustacean_show(token: &String, id: i32) -> Result<Rustacean, Error> {
-    let response = Request::get(&format!("{}/rustaceans/{}", &app_base(), id))
-        .header("Authorization", &format!("Bearer {}", token))
-        .send()
+pub async fn api_rustaceans_page(
+    client: &ApiClient,
+    query: PageQuery,
+) -> Result<Page<Rustacean>, ApiError> {
+    let body: PageBody<Rustacean> = client
+        .get(&format!("/rustaceans?{}", query.to_query_string()))
         .await?;
+    Ok(body.into_page(query))
+}
 
-    response.json::<Rustacean>().await
+pub async fn api_rustacean_show(client: &ApiClient, id: i32) -> Result<Rustacean, ApiError> {
+    client.get(&format!("/rustaceans/{}", id)).await
 }
 
 pub async fn api_rustacean_create(
-    token: &String,
+    client: &ApiClient,
     name: String,
     email: String,
-) -> Result<Rustacean, Error> {
-    let response = Request::post(&format!("{}/rustaceans", &app_base()))
-        .header("Authorization", &format!("Bearer {}", token))
-        .json(&json!({
-            "name": name,
-            "email": email
-        }))?
-        .send()
-        .await?;
-
-    response.json::<Rustacean>().await
+) -> Result<Rustacean, ApiError> {
+    client
+        .post(
+            "/rustaceans",
+            &json!({
+                "name": name,
+                "email": email
+            }),
+        )
+        .await
 }
 
 pub async fn api_rustacean_update(
-    token: &String,
+    client: &ApiClient,
     id: i32,
     name: String,
     email: String,
-) -> Result<Rustacean, Error> {
-    let response = Request::put(&format!("{}/rustaceans/{}", &app_base(), id))
-        .header("Authorization", &format!("Bearer {}", token))
-        .json(&json!({
-            "name": name,
-            "email": email
-        }))?
-        .send()
-        .await?;
-
-    response.json::<Rustacean>().await
+) -> Result<Rustacean, ApiError> {
+    client
+        .put(
+            &format!("/rustaceans/{}", id),
+            &json!({
+                "name": name,
+                "email": email
+            }),
+        )
+        .await
 }
 
-pub async fn api_rustacean_delete(token: &String, id: i32) -> Result<(), Error> {
-    let _ = Request::delete(&format!("{}/rustaceans/{}", &app_base(), id))
-        .header("Authorization", &format!("Bearer {}", token))
-        .send()
-        .await?;
-
-    Ok(())
+pub async fn api_rustacean_delete(client: &ApiClient, id: i32) -> Result<(), ApiError> {
+    client.delete(&format!("/rustaceans/{}", id)).await
 }
@@ -1,15 +1,59 @@
-use gloo_net::http::Request;
-use gloo_net::Error;
 use serde::Deserialize;
 use serde_json::json;
 
-use super::app_base;
+use super::client::ApiClient;
+use super::error::ApiError;
+
+/// Roles of the cr8s backend. Each role includes the ones below it: editors
+/// may also view, admins may do everything.
+#[derive(Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    Admin,
+    Editor,
+    Viewer,
+    /// A role this frontend does not know about; it grants nothing.
+    #[serde(other)]
+    Unknown,
+}
+
+impl Role {
+    pub fn label(self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::Editor => "editor",
+            Role::Viewer => "viewer",
+            Role::Unknown => "unknown",
+        }
+    }
+
+    fn grants(self, required: Role) -> bool {
+        match self {
+            Role::Admin => required != Role::Unknown,
+            Role::Editor => matches!(required, Role::Editor | Role::Viewer),
+            Role::Viewer => required == Role::Viewer,
+            Role::Unknown => false,
+        }
+    }
+}
 
 #[derive(PartialEq)]
 pub struct User {
     pub id: i32,
     pub username: String,
     pub created_at: String,
+    /// `None` when the backend does not report roles; it still enforces them,
+    /// so the UI then offers every action.
+    pub roles: Option<Vec<Role>>,
+}
+
+impl User {
+    pub fn has_role(&self, role: Role) -> bool {
+        match &self.roles {
+            Some(roles) => roles.iter().any(|r| r.grants(role)),
+            None => true,
+        }
+    }
 }
 
 #[derive(Deserialize)]
@@ -22,25 +66,56 @@ pub struct MeResponse {
     pub id: i32,
     pub username: String,
     pub created_at: String,
+    #[serde(default, deserialize_with = "deserialize_roles")]
+    pub roles: Option<Vec<Role>>,
 }
 
-pub async fn api_login(username: String, password: String) -> Result<LoginResponse, Error> {
-    let response = Request::post(&format!("{}/login", &app_base()))
-        .json(&json!({
-            "username": username,
-            "password": password
-        }))?
-        .send()
-        .await?;
+/// Roles arrive either as codes (`["admin", "viewer"]`) or as role records
+/// (`[{"code": "admin", ...}]`).
+fn deserialize_roles<'de, D>(deserializer: D) -> Result<Option<Vec<Role>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RoleEntry {
+        Code(Role),
+        Record { code: Role },
+    }
 
-    response.json::<LoginResponse>().await
+    let entries = Option::<Vec<RoleEntry>>::deserialize(deserializer)?;
+    Ok(entries.map(|entries| {
+        entries
+            .into_iter()
+            .map(|entry| match entry {
+                RoleEntry::Code(role) | RoleEntry::Record { code: role } => role,
+            })
+            .collect()
+    }))
 }
 
-pub async fn api_me(token: &String) -> Result<MeResponse, Error> {
-    let response = Request::get(&format!("{}/me", &app_base()))
-        .header("Authorization", &format!("Bearer {token}"))
-        .send()
-        .await?;
+pub async fn api_login(
+    client: &ApiClient,
+    username: String,
+    password: String,
+) -> Result<LoginResponse, ApiError> {
+    client
+        .post(
+            "/login",
+            &json!({
+                "username": username,
+                "password": password
+            }),
+        )
+        .await
+}
+
+pub async fn api_me(client: &ApiClient) -> Result<MeResponse, ApiError> {
+    client.get("/me").await
+}
 
-    response.json::<MeResponse>().await
+/// Revoke the session of `client`'s token on the backend. The backend has no
+/// endpoint yet to revoke every session of a user.
+pub async fn api_logout(client: &ApiClient) -> Result<(), ApiError> {
+    client.post_empty("/logout").await
 }
@@ -0,0 +1,184 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use yew::Callback;
+
+/// How long a fetched value is served without revalidating it.
+const DEFAULT_TTL_MS: f64 = 30_000.0;
+
+/// In-memory store of API results shared by every `use_api_query` in the app.
+///
+/// Keys name a resource (`crates`, `crates/3`, `rustaceans?page=2`). Entries past
+/// their TTL, or explicitly invalidated, are still served but flagged stale so
+/// the hook shows them while it refetches (stale-while-revalidate).
+#[derive(Clone, Default)]
+pub struct QueryCache {
+    inner: Rc<RefCell<Inner>>,
+}
+
+impl PartialEq for QueryCache {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+#[derive(Default)]
+struct Inner {
+    entries: HashMap<String, Entry>,
+    listeners: Vec<Listener>,
+    next_listener_id: u64,
+}
+
+struct Entry {
+    value: Rc<dyn Any>,
+    fetched_at: f64,
+    invalidated: bool,
+}
+
+struct Listener {
+    id: u64,
+    key: String,
+    callback: Callback<()>,
+}
+
+pub struct Cached<T> {
+    pub value: Rc<T>,
+    pub stale: bool,
+}
+
+/// Drops the listener registered by `QueryCache::subscribe`.
+pub struct Subscription {
+    cache: QueryCache,
+    id: u64,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.cache
+            .inner
+            .borrow_mut()
+            .listeners
+            .retain(|listener| listener.id != self.id);
+    }
+}
+
+impl QueryCache {
+    pub fn get<T: 'static>(&self, key: &str) -> Option<Cached<T>> {
+        let inner = self.inner.borrow();
+        let entry = inner.entries.get(key)?;
+        let value = entry.value.clone().downcast::<T>().ok()?;
+        Some(Cached {
+            value,
+            stale: entry.invalidated || now() - entry.fetched_at > DEFAULT_TTL_MS,
+        })
+    }
+
+    /// Store a freshly fetched value and notify everyone watching `key`.
+    pub fn set<T: 'static>(&self, key: &str, value: Rc<T>) {
+        self.inner.borrow_mut().entries.insert(
+            key.to_string(),
+            Entry {
+                value,
+                fetched_at: now(),
+                invalidated: false,
+            },
+        );
+        self.notify(|k| k == key);
+    }
+
+    /// Rewrite a cached value in place (e.g. patch one row of a list after an
+    /// update) without changing its freshness. No-op if `key` is not cached.
+    pub fn update<T: 'static>(&self, key: &str, f: impl FnOnce(&T) -> T) {
+        {
+            let mut inner = self.inner.borrow_mut();
+            let Some(entry) = inner.entries.get_mut(key) else {
+                return;
+            };
+            let Some(current) = entry.value.downcast_ref::<T>() else {
+                return;
+            };
+            entry.value = Rc::new(f(current));
+        }
+        self.notify(|k| k == key);
+    }
+
+    /// `update` for every `T` cached under `prefix` (matched as by `invalidate`),
+    /// e.g. each cached page of a list. Entries of other types are left alone.
+    pub fn update_all<T: 'static>(&self, prefix: &str, f: impl Fn(&T) -> T) {
+        let mut updated = Vec::new();
+        for (key, entry) in self.inner.borrow_mut().entries.iter_mut() {
+            if !matches_prefix(key, prefix) {
+                continue;
+            }
+            if let Some(current) = entry.value.downcast_ref::<T>() {
+                entry.value = Rc::new(f(current));
+                updated.push(key.clone());
+            }
+        }
+        self.notify(|k| updated.iter().any(|key| key == k));
+    }
+
+    pub fn remove(&self, key: &str) {
+        self.inner.borrow_mut().entries.remove(key);
+        self.notify(|k| k == key);
+    }
+
+    /// Mark `prefix` and everything below it (`prefix/...`, `prefix?...`) stale,
+    /// so mounted queries refetch and unmounted ones do on their next mount.
+    pub fn invalidate(&self, prefix: &str) {
+        for (key, entry) in self.inner.borrow_mut().entries.iter_mut() {
+            if matches_prefix(key, prefix) {
+                entry.invalidated = true;
+            }
+        }
+        self.notify(|k| matches_prefix(k, prefix));
+    }
+
+    pub fn clear(&self) {
+        self.inner.borrow_mut().entries.clear();
+        self.notify(|_| true);
+    }
+
+    pub fn subscribe(&self, key: &str, callback: Callback<()>) -> Subscription {
+        let mut inner = self.inner.borrow_mut();
+        inner.next_listener_id += 1;
+        let id = inner.next_listener_id;
+        inner.listeners.push(Listener {
+            id,
+            key: key.to_string(),
+            callback,
+        });
+        Subscription {
+            cache: self.clone(),
+            id,
+        }
+    }
+
+    fn notify(&self, matches: impl Fn(&str) -> bool) {
+        // Collect first: a listener may re-render and touch the cache.
+        let callbacks: Vec<Callback<()>> = self
+            .inner
+            .borrow()
+            .listeners
+            .iter()
+            .filter(|listener| matches(&listener.key))
+            .map(|listener| listener.callback.clone())
+            .collect();
+        for callback in callbacks {
+            callback.emit(());
+        }
+    }
+}
+
+fn matches_prefix(key: &str, prefix: &str) -> bool {
+    match key.strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with('/') || rest.starts_with('?'),
+        None => false,
+    }
+}
+
+fn now() -> f64 {
+    js_sys::Date::now()
+}
@@ -0,0 +1,201 @@
+use web_sys::Element;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::api::crates::Crate;
+use crate::api::rustaceans::Rustacean;
+use crate::components::header::Header;
+use crate::components::sidebar::Sidebar;
+use crate::hooks::use_cached;
+use crate::Route;
+
+/// Where `PageActions` renders; `None` until the layout has mounted.
+#[derive(Clone, PartialEq)]
+struct ActionsSlot(Option<Element>);
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub children: Children,
+}
+
+/// Page scaffold shared by the protected routes: sidebar on the left, then the
+/// header, a breadcrumb trail for the current route, the page's `PageActions`
+/// and finally the page itself.
+#[function_component(AppLayout)]
+pub fn app_layout(props: &Props) -> Html {
+    // ---
+    let route = use_route::<Route>().unwrap_or(Route::NotFound);
+    let actions_ref = use_node_ref();
+    let slot_handle = use_state(|| ActionsSlot(None));
+
+    {
+        let actions_ref = actions_ref.clone();
+        let slot_handle = slot_handle.clone();
+        use_effect_with((), move |_| {
+            slot_handle.set(ActionsSlot(actions_ref.cast::<Element>()));
+        });
+    }
+
+    html! {
+        <div class="container">
+            <div class="row">
+                <div class="col-sm-auto">
+                    <Sidebar />
+                </div>
+                <div class="col mt-3">
+                    <Header />
+                    <div class="d-flex justify-content-between align-items-center gap-2 mb-3">
+                        <Breadcrumbs route={route} />
+                        <div ref={actions_ref} class="page-actions d-flex gap-1"></div>
+                    </div>
+                    <ContextProvider<ActionsSlot> context={(*slot_handle).clone()}>
+                        {props.children.clone()}
+                    </ContextProvider<ActionsSlot>>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PageActionsProps {
+    pub children: Children,
+}
+
+/// Page-level buttons, shown next to the breadcrumbs of the `AppLayout`.
+#[function_component(PageActions)]
+pub fn page_actions(props: &PageActionsProps) -> Html {
+    match use_context::<ActionsSlot>() {
+        Some(ActionsSlot(Some(host))) => {
+            create_portal(html! { <>{props.children.clone()}</> }, host)
+        }
+        _ => html! {},
+    }
+}
+
+/// One step of the trail. Crates and rustaceans are shown by name.
+#[derive(Clone, PartialEq)]
+enum Crumb {
+    Link(Route, &'static str),
+    Crate(i32),
+    Rustacean(i32),
+    Current(&'static str),
+}
+
+fn trail(route: &Route) -> Vec<Crumb> {
+    let crates = || Crumb::Link(Route::Crates, "Crates");
+    let rustaceans = || Crumb::Link(Route::Rustaceans, "Rustaceans");
+    match route {
+        Route::Crates => vec![Crumb::Current("Crates")],
+        Route::CratesAdd => vec![crates(), Crumb::Current("New")],
+        Route::CratesShow { id } => vec![crates(), Crumb::Crate(*id)],
+        Route::CratesEdit { id } => vec![crates(), Crumb::Crate(*id), Crumb::Current("Edit")],
+        Route::CratesDelete { id } => vec![crates(), Crumb::Crate(*id), Crumb::Current("Delete")],
+        Route::Rustaceans => vec![Crumb::Current("Rustaceans")],
+        Route::RustaceansAdd => vec![rustaceans(), Crumb::Current("New")],
+        Route::RustaceansShow { id } => vec![rustaceans(), Crumb::Rustacean(*id)],
+        Route::RustaceansEdit { id } => {
+            vec![rustaceans(), Crumb::Rustacean(*id), Crumb::Current("Edit")]
+        }
+        Route::RustaceansDelete { id } => {
+            vec![
+                rustaceans(),
+                Crumb::Rustacean(*id),
+                Crumb::Current("Delete"),
+            ]
+        }
+        Route::Home | Route::Login | Route::NotFound => Vec::new(),
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct BreadcrumbsProps {
+    pub route: Route,
+}
+
+/// E.g. Crates › serde › Edit. The last crumb is the current page.
+#[function_component(Breadcrumbs)]
+fn breadcrumbs(props: &BreadcrumbsProps) -> Html {
+    let crumbs = trail(&props.route);
+    if crumbs.is_empty() {
+        return html! { <span></span> };
+    }
+    let last = crumbs.len() - 1;
+
+    html! {
+        <nav aria-label="breadcrumb">
+            <ol class="breadcrumb mb-0">
+                {
+                    crumbs.into_iter().enumerate().map(|(index, crumb)| {
+                        let current = index == last;
+                        html! {
+                            <li
+                                class={classes!("breadcrumb-item", current.then_some("active"))}
+                                aria-current={current.then_some("page")}
+                            >
+                                {
+                                    match crumb {
+                                        Crumb::Link(route, label) => html! {
+                                            <Link<Route> to={route}>{label}</Link<Route>>
+                                        },
+                                        Crumb::Current(label) => html! { label },
+                                        Crumb::Crate(id) => html! { <CrateCrumb id={id} link={!current} /> },
+                                        Crumb::Rustacean(id) => {
+                                            html! { <RustaceanCrumb id={id} link={!current} /> }
+                                        }
+                                    }
+                                }
+                            </li>
+                        }
+                    }).collect::<Html>()
+                }
+            </ol>
+        </nav>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct EntityCrumbProps {
+    pub id: i32,
+    /// Link to the detail page; off when it is the current page.
+    pub link: bool,
+}
+
+// Names come from whatever the page (or an earlier one) already fetched; the
+// trail never sends requests of its own and shows the ID until a name is known.
+
+#[function_component(CrateCrumb)]
+fn crate_crumb(props: &EntityCrumbProps) -> Html {
+    let id = props.id;
+    let single = use_cached::<Crate>(format!("crates/{id}"));
+    let list = use_cached::<Vec<Crate>>("crates".to_string());
+    let name = single.map(|c| c.name.clone()).or_else(|| {
+        list.and_then(|crates| crates.iter().find(|c| c.id == id).map(|c| c.name.clone()))
+    });
+    entity_crumb(name, id, Route::CratesShow { id }, props.link)
+}
+
+#[function_component(RustaceanCrumb)]
+fn rustacean_crumb(props: &EntityCrumbProps) -> Html {
+    let id = props.id;
+    let single = use_cached::<Rustacean>(format!("rustaceans/{id}"));
+    let list = use_cached::<Vec<Rustacean>>("rustaceans".to_string());
+    let name = single.map(|r| r.name.clone()).or_else(|| {
+        list.and_then(|rustaceans| {
+            rustaceans
+                .iter()
+                .find(|r| r.id == id)
+                .map(|r| r.name.clone())
+        })
+    });
+    entity_crumb(name, id, Route::RustaceansShow { id }, props.link)
+}
+
+fn entity_crumb(name: Option<String>, id: i32, route: Route, link: bool) -> Html {
+    let label = name.unwrap_or_else(|| format!("#{id}"));
+    if link {
+        html! { <Link<Route> to={route}>{label}</Link<Route>> }
+    } else {
+        html! { label }
+    }
+}
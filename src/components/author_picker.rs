@@ -0,0 +1,238 @@
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::api::rustaceans::Rustacean;
+use crate::components::modal::Modal;
+use crate::components::rustacean_form::RustaceanForm;
+
+/// Matches listed at once; typing narrows the list further.
+const MAX_MATCHES: usize = 50;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub label: AttrValue,
+    pub name: AttrValue,
+    pub authors: Vec<Rustacean>,
+    /// ID of the selected rustacean, empty when none is.
+    pub value: AttrValue,
+    /// Emitted with the ID of the picked (or newly created) rustacean.
+    pub onchange: Callback<AttrValue>,
+    #[prop_or_default]
+    pub onblur: Callback<FocusEvent>,
+    #[prop_or_default]
+    pub error: Option<AttrValue>,
+}
+
+/// Combobox over rustaceans, matched by name or e-mail. The last entry opens a
+/// `RustaceanForm` in a modal; the new rustacean is selected once saved.
+#[function_component(AuthorPicker)]
+pub fn author_picker(props: &Props) -> Html {
+    // ---
+    let html_id = format!("edit-{}", props.name);
+    let query_handle = use_state(String::default);
+    let open_handle = use_state(|| false);
+    let active_handle = use_state(|| 0_usize);
+    let creating_handle = use_state(|| false);
+
+    let selected = props
+        .authors
+        .iter()
+        .find(|r| r.id.to_string() == props.value.as_str());
+
+    let needle = query_handle.trim().to_lowercase();
+    let matches: Vec<&Rustacean> = props
+        .authors
+        .iter()
+        .filter(|r| {
+            needle.is_empty()
+                || r.name.to_lowercase().contains(&needle)
+                || r.email.to_lowercase().contains(&needle)
+        })
+        .take(MAX_MATCHES)
+        .collect();
+    // One entry past the matches: "Create new rustacean…".
+    let create_index = matches.len();
+
+    let pick = {
+        let onchange = props.onchange.clone();
+        let open_handle = open_handle.clone();
+        let query_handle = query_handle.clone();
+        Callback::from(move |id: i32| {
+            onchange.emit(AttrValue::from(id.to_string()));
+            query_handle.set(String::default());
+            open_handle.set(false);
+        })
+    };
+    let start_create = {
+        let creating_handle = creating_handle.clone();
+        let open_handle = open_handle.clone();
+        Callback::from(move |_| {
+            open_handle.set(false);
+            creating_handle.set(true);
+        })
+    };
+
+    let oninput = {
+        let query_handle = query_handle.clone();
+        let open_handle = open_handle.clone();
+        let active_handle = active_handle.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                query_handle.set(input.value());
+                open_handle.set(true);
+                active_handle.set(0);
+            }
+        })
+    };
+
+    let onkeydown = {
+        let open_handle = open_handle.clone();
+        let active_handle = active_handle.clone();
+        let pick = pick.clone();
+        let start_create = start_create.clone();
+        let ids: Vec<i32> = matches.iter().map(|r| r.id).collect();
+        Callback::from(move |e: KeyboardEvent| match e.key().as_str() {
+            "ArrowDown" => {
+                e.prevent_default();
+                open_handle.set(true);
+                active_handle.set((*active_handle + 1).min(create_index));
+            }
+            "ArrowUp" => {
+                e.prevent_default();
+                active_handle.set(active_handle.saturating_sub(1));
+            }
+            // Enter would otherwise submit the crate form.
+            "Enter" if *open_handle => {
+                e.prevent_default();
+                match ids.get(*active_handle) {
+                    Some(id) => pick.emit(*id),
+                    None => start_create.emit(()),
+                }
+            }
+            "Escape" => open_handle.set(false),
+            _ => {}
+        })
+    };
+
+    let onfocus = {
+        let open_handle = open_handle.clone();
+        let active_handle = active_handle.clone();
+        Callback::from(move |_: FocusEvent| {
+            open_handle.set(true);
+            active_handle.set(0);
+        })
+    };
+    let onblur = {
+        let open_handle = open_handle.clone();
+        let query_handle = query_handle.clone();
+        let onblur = props.onblur.clone();
+        Callback::from(move |e: FocusEvent| {
+            open_handle.set(false);
+            query_handle.set(String::default());
+            onblur.emit(e);
+        })
+    };
+
+    let item_classes = |index: usize| {
+        if index == *active_handle {
+            classes!("list-group-item", "list-group-item-action", "active")
+        } else {
+            classes!("list-group-item", "list-group-item-action")
+        }
+    };
+    let items = matches
+        .iter()
+        .enumerate()
+        .map(|(index, rustacean)| {
+            let id = rustacean.id;
+            let pick = pick.clone();
+            // Mousedown rather than click: the input's blur would close the list first.
+            let onmousedown = Callback::from(move |e: MouseEvent| {
+                e.prevent_default();
+                pick.emit(id);
+            });
+            html! {
+                <li
+                    class={item_classes(index)}
+                    role="option"
+                    aria-selected={(index == *active_handle).to_string()}
+                    onmousedown={onmousedown}
+                >
+                    <div>{rustacean.name.clone()}</div>
+                    <small>{rustacean.email.clone()}</small>
+                </li>
+            }
+        })
+        .collect::<Html>();
+    let create_mousedown = {
+        let start_create = start_create.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            start_create.emit(());
+        })
+    };
+
+    let close_modal = {
+        let creating_handle = creating_handle.clone();
+        Callback::from(move |_| creating_handle.set(false))
+    };
+    let created = {
+        let creating_handle = creating_handle.clone();
+        let pick = pick.clone();
+        Callback::from(move |rustacean: Rustacean| {
+            creating_handle.set(false);
+            pick.emit(rustacean.id);
+        })
+    };
+
+    let shown_value = if *open_handle {
+        (*query_handle).clone()
+    } else {
+        selected.map(|r| r.name.clone()).unwrap_or_default()
+    };
+
+    html! {
+        <div class="author-picker position-relative">
+            <label for={html_id.clone()}>{props.label.clone()}</label>
+            <input
+                id={html_id}
+                type="text"
+                class={classes!("form-control", props.error.is_some().then_some("is-invalid"))}
+                name={format!("{}-search", props.name)}
+                placeholder={if *open_handle { "Search by name or e-mail" } else { "Select an author" }}
+                autocomplete="off"
+                role="combobox"
+                aria-expanded={open_handle.to_string()}
+                value={shown_value}
+                oninput={oninput}
+                onkeydown={onkeydown}
+                onfocus={onfocus}
+                onblur={onblur}
+            />
+            <input type="hidden" name={props.name.clone()} value={props.value.clone()} />
+            if let Some(error) = &props.error {
+                <div class="invalid-feedback">{error.clone()}</div>
+            }
+            if *open_handle {
+                <ul class="list-group position-absolute w-100 shadow-sm author-picker-options" role="listbox">
+                    {items}
+                    if matches.is_empty() {
+                        <li class="list-group-item text-muted">{"No matching rustaceans"}</li>
+                    }
+                    <li
+                        class={classes!(item_classes(create_index), "fst-italic")}
+                        role="option"
+                        onmousedown={create_mousedown}
+                    >
+                        {"Create new rustacean…"}
+                    </li>
+                </ul>
+            }
+            if *creating_handle {
+                <Modal title="New rustacean" onclose={close_modal}>
+                    <RustaceanForm onsaved={created} id_prefix="author-" />
+                </Modal>
+            }
+        </div>
+    }
+}
@@ -5,6 +5,9 @@ pub struct Props {
     pub label: AttrValue,
     pub button_type: AttrValue,
 
+    #[prop_or_default]
+    pub disabled: bool,
+
     #[prop_or_default]
     pub onclick: Option<Callback<MouseEvent>>,
 }
@@ -15,12 +18,12 @@ pub fn button(props: &Props) -> Html {
     let classes = classes!("btn", format!("btn-{}", props.button_type));
     match props.onclick.clone() {
         Some(callback) => html! {
-            <button type="submit" class={classes} onclick={callback}>
+            <button type="submit" class={classes} disabled={props.disabled} onclick={callback}>
                 {props.label.clone()}
             </button>
         },
         None => html! {
-            <button type="submit" class={classes}>
+            <button type="submit" class={classes} disabled={props.disabled}>
                 {props.label.clone()}
             </button>
         },
@@ -1,16 +1,27 @@
-use web_sys::{HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
 use yew::{platform::spawn_local, prelude::*};
 use yew_router::prelude::*;
 
 use crate::api::crates::{api_crate_create, api_crate_update, Crate};
 use crate::api::rustaceans::Rustacean;
+use crate::cache::QueryCache;
 use crate::components::alert::Alert;
+use crate::components::author_picker::AuthorPicker;
+use crate::components::button::Button;
+use crate::components::draft_notice::DraftNotice;
 use crate::components::input::Input;
-use crate::components::select::Select;
 use crate::components::textarea::Textarea;
 use crate::contexts::CurrentUserContext;
+use crate::drafts::use_draft;
+use crate::form::{crate_code, max_length, required, semver, use_form, Field};
+use crate::hooks::crate_saved;
+use crate::releases;
+use crate::semver::{Bump, Version};
 use crate::Route;
 
+const MAX_CODE_LENGTH: usize = 64;
+const MAX_NAME_LENGTH: usize = 64;
+const MAX_DESCRIPTION_LENGTH: usize = 5000;
+
 #[derive(Properties, PartialEq)]
 pub struct Props {
     #[prop_or_default]
@@ -24,157 +35,161 @@ pub fn crate_form(props: &Props) -> Html {
     let navigator = use_navigator().expect("Navigator not available");
     let current_user_ctx =
         use_context::<CurrentUserContext>().expect("Current user context is missing");
+    let cache = use_context::<QueryCache>().expect("Query cache context is missing");
 
-    let name_handle = use_state(|| {
-        if let Some(c) = &props.cr8 {
-            return c.name.clone();
-        }
-        String::default()
-    });
-    let name = (*name_handle).clone();
-    let code_handle = use_state(|| {
-        if let Some(c) = &props.cr8 {
-            return c.code.clone();
-        }
-        String::default()
-    });
-    let code = (*code_handle).clone();
-    let rustacean_id_handle = use_state(|| {
-        if let Some(c) = &props.cr8 {
-            return c.rustacean_id.to_string();
-        }
-        String::default()
-    });
-    let rustacean_id = (*rustacean_id_handle).clone();
-    let version_handle = use_state(|| {
-        if let Some(c) = &props.cr8 {
-            return c.version.clone();
-        }
-        String::default()
-    });
-    let version = (*version_handle).clone();
-    let description_handle = use_state(|| {
-        if let Some(c) = &props.cr8 {
-            if let Some(description) = c.description.clone() {
-                return description;
-            }
-        }
-        String::default()
+    let cr8 = props.cr8.clone();
+    let form = use_form(move || {
+        let initial = |value: fn(&Crate) -> String| cr8.as_ref().map(value).unwrap_or_default();
+        vec![
+            Field::new("code", initial(|c| c.code.clone()))
+                .validate(required())
+                .validate(crate_code())
+                .validate(max_length(MAX_CODE_LENGTH))
+                .on_conflict("A crate with this code already exists"),
+            Field::new("name", initial(|c| c.name.clone()))
+                .validate(required())
+                .validate(max_length(MAX_NAME_LENGTH)),
+            Field::new("version", initial(|c| c.version.clone()))
+                .validate(required())
+                .validate(semver()),
+            Field::new("author", initial(|c| c.rustacean_id.to_string()))
+                .validate(required())
+                .server_name("rustacean_id"),
+            Field::new(
+                "description",
+                initial(|c| c.description.clone().unwrap_or_default()),
+            )
+            .validate(max_length(MAX_DESCRIPTION_LENGTH)),
+        ]
     });
-    let description = (*description_handle).clone();
+    let draft_key = match &props.cr8 {
+        Some(existing) => format!("crate-{}", existing.id),
+        None => "crate-new".to_string(),
+    };
+    let draft = use_draft(draft_key.clone(), &form);
     let error_message_handle = use_state(String::default);
     let error_message = (*error_message_handle).clone();
+    let bump_menu_handle = use_state(|| false);
 
-    let name_changed = Callback::from(move |e: Event| {
-        let target = e.target_dyn_into::<HtmlInputElement>();
-        if let Some(input) = target {
-            name_handle.set(input.value());
-        }
-    });
-
-    let code_changed = Callback::from(move |e: Event| {
-        let target = e.target_dyn_into::<HtmlInputElement>();
-        if let Some(input) = target {
-            code_handle.set(input.value());
-        }
-    });
-
-    let rustacean_id_changed = Callback::from(move |e: Event| {
-        let target = e.target_dyn_into::<HtmlSelectElement>();
-        if let Some(input) = target {
-            rustacean_id_handle.set(input.value());
-        }
-    });
+    let version = form.value("version");
 
-    let version_changed = Callback::from(move |e: Event| {
-        let target = e.target_dyn_into::<HtmlInputElement>();
-        if let Some(input) = target {
-            version_handle.set(input.value());
-        }
-    });
+    // Versions only move forward in our release workflow, so edits are checked
+    // against the version the crate had when the form was opened.
+    let current_version = props
+        .cr8
+        .as_ref()
+        .and_then(|c| Version::parse(&c.version).ok());
+    let is_downgrade = match (&current_version, Version::parse(&version)) {
+        (Some(current), Ok(new)) => new < *current,
+        _ => false,
+    };
 
-    let description_changed = Callback::from(move |e: Event| {
-        let target = e.target_dyn_into::<HtmlTextAreaElement>();
-        if let Some(input) = target {
-            description_handle.set(input.value());
-        }
+    let bump_items = current_version.as_ref().map(|current| {
+        Bump::ALL
+            .iter()
+            // A bump that would overflow is left out.
+            .filter_map(|bump| current.bump(*bump).map(|next| (bump, next.to_string())))
+            .map(|(bump, next)| {
+                let form = form.clone();
+                let bump_menu_handle = bump_menu_handle.clone();
+                let label = format!("{} ({next})", bump.label());
+                let onclick = Callback::from(move |_: MouseEvent| {
+                    form.set("version", next.clone());
+                    bump_menu_handle.set(false);
+                });
+                html! {
+                    <li>
+                        <button type="button" class="dropdown-item" onclick={onclick}>
+                            {label}
+                        </button>
+                    </li>
+                }
+            })
+            .collect::<Html>()
     });
+    let toggle_bump_menu = {
+        let bump_menu_handle = bump_menu_handle.clone();
+        Callback::from(move |_: MouseEvent| bump_menu_handle.set(!*bump_menu_handle))
+    };
 
-    // The undscore names are cloned values to moving into the blocks below.
-    //
-    let name_ = name.clone();
-    let code_ = code.clone();
-    let version_ = version.clone();
-    let rustacean_id_ = rustacean_id.clone();
-    let description_ = description.clone();
+    let discard_draft = draft.discard.clone();
+    let form_ = form.clone();
     let crate_ = props.cr8.clone();
+    let current_version_ = current_version.clone();
     let onsubmit = Callback::from(move |e: SubmitEvent| {
         e.prevent_default();
 
-        let name_ = name_.clone();
-        let code_ = code_.clone();
+        let version_ = form_.value("version");
+        if is_downgrade && !confirm_downgrade(current_version_.as_ref(), &version_) {
+            return;
+        }
+        if !form_.begin_submit() {
+            return;
+        }
+
+        let form_ = form_.clone();
         let crate_ = crate_.clone();
-        let version_ = version_.clone();
-        let description_ = description_.clone();
         let error_handle_ = error_message_handle.clone();
         let navigator_ = navigator.clone();
-        let user_ctx_ = current_user_ctx.clone();
-        match &user_ctx_.token {
-            Some(token) => {
-                let rustacean_id = rustacean_id_.parse::<i32>();
-                let token = token.clone();
-                match rustacean_id {
-                    Ok(rustacean_id) => spawn_local(async move {
-                        if let Some(cr8) = crate_ {
-                            match api_crate_update(
-                                &token,
-                                cr8.id,
-                                name_,
-                                code_,
-                                rustacean_id,
-                                version_,
-                                description_,
-                            )
-                            .await
-                            {
-                                Ok(_) => navigator_.push(&Route::Crates),
-                                Err(e) => error_handle_.set(e.to_string()),
-                            }
-                        } else {
-                            match api_crate_create(
-                                &token,
-                                name_,
-                                code_,
-                                rustacean_id,
-                                version_,
-                                description_,
-                            )
-                            .await
-                            {
-                                Ok(_) => navigator_.push(&Route::Crates),
-                                Err(e) => error_handle_.set(e.to_string()),
-                            }
-                        }
-                    }),
-                    Err(_) => error_handle_.set("Cannot parse rustacean ID".to_string()),
+        let cache_ = cache.clone();
+        let discard_draft_ = discard_draft.clone();
+        let scope_ = current_user_ctx.storage_scope();
+        let name_ = form_.value("name");
+        let code_ = form_.value("code");
+        let description_ = form_.value("description");
+        let Some(client) = current_user_ctx.client() else {
+            error_handle_.set("Session expired. Please login again".to_string());
+            form_.end_submit();
+            return;
+        };
+        let Ok(rustacean_id) = form_.value("author").parse::<i32>() else {
+            error_handle_.set("Cannot parse rustacean ID".to_string());
+            form_.end_submit();
+            return;
+        };
+        spawn_local(async move {
+            let result = match &crate_ {
+                Some(cr8) => {
+                    api_crate_update(
+                        &client,
+                        cr8.id,
+                        name_,
+                        code_,
+                        rustacean_id,
+                        version_,
+                        description_,
+                    )
+                    .await
+                }
+                None => {
+                    api_crate_create(&client, name_, code_, rustacean_id, version_, description_)
+                        .await
+                }
+            };
+            match result {
+                Ok(saved) => {
+                    if let (Some(cr8), Some(scope)) = (&crate_, &scope_) {
+                        releases::record_update(scope, cr8, &saved);
+                    }
+                    crate_saved(&cache_, &saved);
+                    discard_draft_.emit(());
+                    navigator_.push(&Route::Crates)
                 }
+                Err(e) => error_handle_.set(form_.fail_submit(&e).unwrap_or_default()),
             }
-            None => error_handle_.set("Session expired. Please login again".to_string()),
-        }
+        });
     });
 
-    let options = props
-        .authors
-        .iter()
-        .map(|r| {
-            (
-                AttrValue::from(r.id.to_string()),
-                AttrValue::from(r.name.clone()),
-            )
-        })
-        .collect::<Vec<(AttrValue, AttrValue)>>();
+    let author_changed = {
+        let form = form.clone();
+        Callback::from(move |id: AttrValue| form.set("author", id.to_string()))
+    };
+
     html! {
         <form onsubmit={onsubmit}>
+            if let Some(pending) = draft.pending.clone() {
+                <DraftNotice draft={pending} onrestore={draft.restore} ondiscard={draft.discard} />
+            }
             if !error_message.is_empty() {
                 <Alert alert_type={"danger"} message={error_message} />
             }
@@ -183,8 +198,10 @@ pub fn crate_form(props: &Props) -> Html {
                     input_type="text"
                     name="code"
                     label="Code"
-                    value={code}
-                    onchange={code_changed}
+                    value={form.value("code")}
+                    oninput={form.oninput("code")}
+                    onblur={form.onblur("code")}
+                    error={form.error("code")}
                 />
             </div>
             <div class="mb-3">
@@ -192,8 +209,10 @@ pub fn crate_form(props: &Props) -> Html {
                     input_type="text"
                     name="name"
                     label="Name"
-                    value={name}
-                    onchange={name_changed}
+                    value={form.value("name")}
+                    oninput={form.oninput("name")}
+                    onblur={form.onblur("name")}
+                    error={form.error("name")}
                 />
             </div>
             <div class="mb-3">
@@ -201,28 +220,78 @@ pub fn crate_form(props: &Props) -> Html {
                     input_type="text"
                     name="version"
                     label="Version"
-                    value={version}
-                    onchange={version_changed}
+                    value={version.clone()}
+                    oninput={form.oninput("version")}
+                    onblur={form.onblur("version")}
+                    error={form.error("version")}
                 />
+                if let Some(bump_items) = bump_items {
+                    <div class="dropdown mt-1">
+                        <button
+                            type="button"
+                            class="btn btn-sm btn-outline-secondary dropdown-toggle"
+                            onclick={toggle_bump_menu}
+                        >
+                            {"Bump version"}
+                        </button>
+                        <ul class={classes!("dropdown-menu", bump_menu_handle.then_some("show"))}>
+                            {bump_items}
+                        </ul>
+                    </div>
+                }
+                if is_downgrade {
+                    <div class="form-text text-warning">
+                        {format!(
+                            "{} is lower than the current version {}. Releases are expected to only move forward.",
+                            version,
+                            current_version.as_ref().map(ToString::to_string).unwrap_or_default(),
+                        )}
+                    </div>
+                }
             </div>
             <div class="mb-3">
-                <Select
+                <AuthorPicker
                     name="author"
                     label="Author"
-                    value={rustacean_id}
-                    onchange={rustacean_id_changed}
-                    options={options}
+                    authors={props.authors.clone()}
+                    value={form.value("author")}
+                    onchange={author_changed}
+                    onblur={form.onblur("author")}
+                    error={form.error("author")}
                 />
             </div>
             <div class="mb-3">
                 <Textarea
                     name="description"
                     label="Description"
-                    value={description}
-                    onchange={description_changed}
+                    value={form.value("description")}
+                    oninput={form.oninput("description")}
+                    onblur={form.onblur("description")}
+                    error={form.error("description")}
+                    markdown=true
                 />
             </div>
-            <button type="submit" class="btn btn-primary">{"Save"}</button>
+            <Button
+                button_type="primary"
+                label={if form.is_submitting() { "Saving ..." } else { "Save" }}
+                disabled={form.is_submitting()}
+            />
+            if form.is_dirty() && !form.is_submitting() {
+                <span class="ms-2 text-muted">{"Unsaved changes"}</span>
+            }
         </form>
     }
 }
+
+/// Ask before saving a version lower than the current one.
+fn confirm_downgrade(current: Option<&Version>, new: &str) -> bool {
+    let Some(window) = web_sys::window() else {
+        return true;
+    };
+    let current = current.map(ToString::to_string).unwrap_or_default();
+    window
+        .confirm_with_message(&format!(
+            "Version {new} is lower than the current version {current}. Save anyway?"
+        ))
+        .unwrap_or(false)
+}
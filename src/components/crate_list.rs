@@ -1,67 +1,148 @@
-use crate::hooks::use_crates;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::api::client::ApiClient;
+use crate::api::crates::Crate;
+use crate::api::page::PageQuery;
+use crate::api::user::Role;
+use crate::components::app_layout::PageActions;
+use crate::components::data_table::{Column, DataTable};
+use crate::components::markdown::{Markdown, EXCERPT_LENGTH};
+use crate::components::pagination::Pagination;
+use crate::components::query_status::QueryStatus;
+use crate::format::format_timestamp;
+use crate::hooks::{use_crates_page, use_has_role, use_rustaceans};
 use crate::Route;
 use yew::prelude::*;
 use yew_router::prelude::*;
 
 #[derive(Properties, PartialEq)]
 pub struct Props {
-    pub token: AttrValue,
+    pub client: ApiClient,
 }
 
 #[function_component(CrateList)]
-pub fn crate_list(props: &Props) -> HtmlResult {
-    let crates = use_crates(&props.token)?;
+pub fn crate_list(props: &Props) -> Html {
+    let navigator = use_navigator().expect("Navigator not available");
+    let can_edit = use_has_role(Role::Editor);
+    let page_query = use_location()
+        .and_then(|location| location.query::<PageQuery>().ok())
+        .unwrap_or_default()
+        .normalized();
+    let query = use_crates_page(&props.client, page_query);
+    let rustaceans = use_rustaceans(&props.client);
+    let Some(page) = query.data.clone() else {
+        return html! {
+            <QueryStatus
+                error={query.error.clone()}
+                loading={query.loading}
+                onretry={query.refetch.clone()}
+            />
+        };
+    };
 
-    Ok(html! {
-        <>
-            <p>
-                <Link<Route> to={Route::CratesAdd}>
-                    {"+ Add new crate"}
+    // Author names are looked up in the full rustacean list, fetched once and
+    // shared through the query cache.
+    let authors_loaded = rustaceans.data.is_some();
+    let authors: Rc<HashMap<i32, String>> = Rc::new(
+        rustaceans
+            .data
+            .iter()
+            .flat_map(|rustaceans| rustaceans.iter())
+            .map(|r| (r.id, r.name.clone()))
+            .collect(),
+    );
+
+    let onpagechange = Callback::from(move |page_query: PageQuery| {
+        if let Err(e) = navigator.push_with_query(&Route::Crates, &page_query) {
+            log::warn!("Failed to change page: {e}");
+        }
+    });
+
+    let mut columns = vec![
+        Column::new("id", "ID", |c: &Crate| c.id.to_string()),
+        Column::new("code", "Code", |c: &Crate| c.code.clone()),
+        Column::new("name", "Name", |c: &Crate| c.name.clone()).render(|c: &Crate| {
+            html! {
+                <Link<Route> to={Route::CratesShow { id: c.id }}>{c.name.clone()}</Link<Route>>
+            }
+        }),
+        Column::new("author", "Author", {
+            let authors = authors.clone();
+            move |c: &Crate| match authors.get(&c.rustacean_id) {
+                Some(name) => name.clone(),
+                None => format!("#{}", c.rustacean_id),
+            }
+        })
+        .render(move |c: &Crate| match authors.get(&c.rustacean_id) {
+            Some(name) => html! {
+                <Link<Route> to={Route::RustaceansShow { id: c.rustacean_id }}>
+                    {name.clone()}
                 </Link<Route>>
-            </p>
-            <table class="table">
-                <thead>
-                    <th>{"ID"}</th>
-                    <th>{"Code"}</th>
-                    <th>{"Name"}</th>
-                    <th>{"Rustacean ID"}</th>
-                    <th>{"Version"}</th>
-                    <th>{"Description"}</th>
-                    <th>{"Created at"}</th>
-                    <th>{"Operations"}</th>
-                </thead>
-                <tbody>
-                {
-                    crates.into_iter().map(|cr8| {
-                        html! {
-                            <tr>
-                                <td>{cr8.id}</td>
-                                <td>{cr8.code}</td>
-                                <td>{cr8.name}</td>
-                                <td>{cr8.rustacean_id}</td>
-                                <td>{cr8.version}</td>
-                                <td>{cr8.description}</td>
-                                <td>
-                                    <Link<Route>
-                                        to={Route::CratesEdit { id: cr8.id }}
-                                        classes="link-secondary"
-                                    >
-                                        {"edit"}
-                                    </Link<Route>>
-                                    <span class="mx-1">{"/"}</span>
-                                    <Link<Route>
-                                        to={Route::CratesDelete { id: cr8.id }}
-                                        classes="link-danger"
-                                    >
-                                        {"delete"}
-                                    </Link<Route>>
-                                </td>
-                            </tr>
-                        }
-                    }).collect::<Html>()
-                }
-                </tbody>
-            </table>
+            },
+            // Names are loaded but this ID is not among them: the author was deleted.
+            None if authors_loaded => html! {
+                <span class="text-muted" title="This rustacean no longer exists">
+                    {format!("Unknown author (#{})", c.rustacean_id)}
+                </span>
+            },
+            None => html! { <span class="text-muted">{format!("#{}", c.rustacean_id)}</span> },
+        }),
+        Column::new("version", "Version", |c: &Crate| c.version.clone()),
+        Column::new("description", "Description", |c: &Crate| {
+            c.description.clone().unwrap_or_default()
+        })
+        .render(|c: &Crate| {
+            html! {
+                <Markdown
+                    source={c.description.clone().unwrap_or_default()}
+                    limit={EXCERPT_LENGTH}
+                />
+            }
+        }),
+        Column::new("created_at", "Created at", |c: &Crate| {
+            format_timestamp(&c.created_at)
+        }),
+    ];
+    if can_edit {
+        columns.push(Column::actions("operations", "Operations", |c: &Crate| {
+            html! {
+                <>
+                    <Link<Route>
+                        to={Route::CratesEdit { id: c.id }}
+                        classes="link-secondary"
+                    >
+                        {"edit"}
+                    </Link<Route>>
+                    <span class="mx-1">{"/"}</span>
+                    <Link<Route>
+                        to={Route::CratesDelete { id: c.id }}
+                        classes="link-danger"
+                    >
+                        {"delete"}
+                    </Link<Route>>
+                </>
+            }
+        }));
+    }
+
+    html! {
+        <>
+            if can_edit {
+                <PageActions>
+                    <Link<Route> to={Route::CratesAdd} classes="btn btn-sm btn-primary">
+                        {"+ Add new crate"}
+                    </Link<Route>>
+                </PageActions>
+            }
+            <DataTable<Crate> id="crates" columns={columns} rows={Rc::new(page.items.clone())} paged=true />
+            <Pagination
+                query={page.query}
+                count={page.items.len()}
+                total={page.total}
+                has_next={page.has_next()}
+                onchange={onpagechange}
+            />
         </>
-    })
+    }
 }
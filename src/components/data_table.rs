@@ -0,0 +1,326 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use gloo_storage::{LocalStorage, Storage};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+type CellRenderer<T> = Rc<dyn Fn(&T) -> Html>;
+
+/// One column of a `DataTable`. `text` feeds sorting and filtering and is what the
+/// cell shows unless the column renders its own markup.
+pub struct Column<T> {
+    pub key: &'static str,
+    pub label: &'static str,
+    text: Rc<dyn Fn(&T) -> String>,
+    render: Option<CellRenderer<T>>,
+    sortable: bool,
+    filterable: bool,
+}
+
+impl<T> Column<T> {
+    pub fn new(
+        key: &'static str,
+        label: &'static str,
+        text: impl Fn(&T) -> String + 'static,
+    ) -> Self {
+        Self {
+            key,
+            label,
+            text: Rc::new(text),
+            render: None,
+            sortable: true,
+            filterable: true,
+        }
+    }
+
+    /// A column without data of its own, e.g. row actions.
+    pub fn actions(
+        key: &'static str,
+        label: &'static str,
+        render: impl Fn(&T) -> Html + 'static,
+    ) -> Self {
+        Self {
+            key,
+            label,
+            text: Rc::new(|_| String::new()),
+            render: Some(Rc::new(render)),
+            sortable: false,
+            filterable: false,
+        }
+    }
+
+    /// Show custom markup in the cell; `text` still drives sorting and filtering.
+    pub fn render(mut self, render: impl Fn(&T) -> Html + 'static) -> Self {
+        self.render = Some(Rc::new(render));
+        self
+    }
+}
+
+impl<T> Clone for Column<T> {
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key,
+            label: self.label,
+            text: self.text.clone(),
+            render: self.render.clone(),
+            sortable: self.sortable,
+            filterable: self.filterable,
+        }
+    }
+}
+
+// Closures are rebuilt on every parent render, so comparing them by pointer
+// means a table re-renders whenever its columns are redefined.
+impl<T> PartialEq for Column<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+            && self.label == other.label
+            && Rc::ptr_eq(&self.text, &other.text)
+            && match (&self.render, &other.render) {
+                (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+                (None, None) => true,
+                _ => false,
+            }
+            && self.sortable == other.sortable
+            && self.filterable == other.filterable
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props<T: PartialEq> {
+    /// Names the table in local storage, where hidden columns are remembered.
+    pub id: AttrValue,
+    pub columns: Vec<Column<T>>,
+    pub rows: Rc<Vec<T>>,
+    /// `rows` is one page of a longer list. Sorting and filtering only see that
+    /// page, which the headers and filter boxes then say.
+    #[prop_or_default]
+    pub paged: bool,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+struct Sort {
+    key: &'static str,
+    ascending: bool,
+}
+
+#[function_component(DataTable)]
+pub fn data_table<T>(props: &Props<T>) -> Html
+where
+    T: PartialEq + 'static,
+{
+    // ---
+    let storage_key = format!("cr8s_table_{}_hidden", props.id);
+    let sort_handle = use_state(|| None::<Sort>);
+    let filters_handle = use_state(HashMap::<&'static str, String>::default);
+    let hidden_handle = {
+        let storage_key = storage_key.clone();
+        use_state(move || LocalStorage::get::<HashSet<String>>(&storage_key).unwrap_or_default())
+    };
+    let menu_open_handle = use_state(|| false);
+
+    let visible: Vec<&Column<T>> = props
+        .columns
+        .iter()
+        .filter(|column| !hidden_handle.contains(column.key))
+        .collect();
+
+    let mut rows: Vec<&T> = props
+        .rows
+        .iter()
+        .filter(|row| {
+            filters_handle.iter().all(|(key, needle)| {
+                let needle = needle.trim().to_lowercase();
+                needle.is_empty()
+                    || props
+                        .columns
+                        .iter()
+                        .find(|column| column.key == *key)
+                        .is_none_or(|column| (column.text)(row).to_lowercase().contains(&needle))
+            })
+        })
+        .collect();
+
+    if let Some(sort) = *sort_handle {
+        if let Some(column) = props.columns.iter().find(|column| column.key == sort.key) {
+            rows.sort_by(|a, b| {
+                let ordering = compare_cells(&(column.text)(a), &(column.text)(b));
+                if sort.ascending {
+                    ordering
+                } else {
+                    ordering.reverse()
+                }
+            });
+        }
+    }
+
+    let scope = if props.paged { " on this page" } else { "" };
+
+    let header = |column: &Column<T>| {
+        if !column.sortable {
+            return html! { <th>{column.label}</th> };
+        }
+        let key = column.key;
+        let sort_handle = sort_handle.clone();
+        let indicator = match *sort_handle {
+            Some(Sort {
+                key: k,
+                ascending: true,
+            }) if k == key => " ▲",
+            Some(Sort {
+                key: k,
+                ascending: false,
+            }) if k == key => " ▼",
+            _ => "",
+        };
+        let onclick = Callback::from(move |_: MouseEvent| {
+            // Cycle ascending -> descending -> unsorted.
+            sort_handle.set(match *sort_handle {
+                Some(Sort {
+                    key: k,
+                    ascending: true,
+                }) if k == key => Some(Sort {
+                    key,
+                    ascending: false,
+                }),
+                Some(Sort {
+                    key: k,
+                    ascending: false,
+                }) if k == key => None,
+                _ => Some(Sort {
+                    key,
+                    ascending: true,
+                }),
+            });
+        });
+        html! {
+            <th class="sortable" onclick={onclick} role="button" title={format!("Sort rows{scope}")}>
+                {column.label}{indicator}
+            </th>
+        }
+    };
+
+    let filter_cell = |column: &Column<T>| {
+        if !column.filterable {
+            return html! { <th></th> };
+        }
+        let key = column.key;
+        let filters_handle = filters_handle.clone();
+        let value = filters_handle.get(key).cloned().unwrap_or_default();
+        let oninput = Callback::from(move |e: InputEvent| {
+            if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                let mut filters = (*filters_handle).clone();
+                filters.insert(key, input.value());
+                filters_handle.set(filters);
+            }
+        });
+        html! {
+            <th>
+                <input
+                    type="search"
+                    class="form-control form-control-sm"
+                    name={format!("filter-{key}")}
+                    placeholder={format!("Filter{scope}")}
+                    value={value}
+                    oninput={oninput}
+                />
+            </th>
+        }
+    };
+
+    let toggle_menu = {
+        let menu_open_handle = menu_open_handle.clone();
+        Callback::from(move |_: MouseEvent| menu_open_handle.set(!*menu_open_handle))
+    };
+
+    let column_toggle = |column: &Column<T>| {
+        let key = column.key;
+        let hidden_handle = hidden_handle.clone();
+        let storage_key = storage_key.clone();
+        let checked = !hidden_handle.contains(key);
+        let onchange = Callback::from(move |_: Event| {
+            let mut hidden = (*hidden_handle).clone();
+            if !hidden.remove(key) {
+                hidden.insert(key.to_string());
+            }
+            if let Err(e) = LocalStorage::set(&storage_key, &hidden) {
+                log::warn!("Could not persist hidden columns: {e}");
+            }
+            hidden_handle.set(hidden);
+        });
+        let html_id = format!("column-{}", key);
+        html! {
+            <div class="form-check">
+                <input
+                    id={html_id.clone()}
+                    class="form-check-input"
+                    type="checkbox"
+                    checked={checked}
+                    onchange={onchange}
+                />
+                <label class="form-check-label" for={html_id}>{column.label}</label>
+            </div>
+        }
+    };
+
+    html! {
+        <div class="data-table">
+            <div class="d-flex justify-content-end mb-2 position-relative">
+                <button type="button" class="btn btn-sm btn-outline-secondary" onclick={toggle_menu}>
+                    {"Columns"}
+                </button>
+                if *menu_open_handle {
+                    <div class="card position-absolute end-0 top-100 p-2 shadow-sm data-table-menu">
+                        { props.columns.iter().map(column_toggle).collect::<Html>() }
+                    </div>
+                }
+            </div>
+            <div class="data-table-scroll">
+                <table class="table">
+                    <thead>
+                        <tr>{ visible.iter().map(|column| header(column)).collect::<Html>() }</tr>
+                        <tr>{ visible.iter().map(|column| filter_cell(column)).collect::<Html>() }</tr>
+                    </thead>
+                    <tbody>
+                        if rows.is_empty() {
+                            <tr>
+                                <td colspan={visible.len().max(1).to_string()} class="text-center text-muted">
+                                    {format!("No matching rows{scope}")}
+                                </td>
+                            </tr>
+                        }
+                        {
+                            rows.iter().map(|row| html! {
+                                <tr>
+                                    {
+                                        visible.iter().map(|column| html! {
+                                            <td>
+                                                {
+                                                    match &column.render {
+                                                        Some(render) => render(row),
+                                                        None => html! { {(column.text)(row)} },
+                                                    }
+                                                }
+                                            </td>
+                                        }).collect::<Html>()
+                                    }
+                                </tr>
+                            }).collect::<Html>()
+                        }
+                    </tbody>
+                </table>
+            </div>
+        </div>
+    }
+}
+
+/// Numbers compare numerically (so ID 9 sorts before 10), everything else
+/// case-insensitively.
+fn compare_cells(a: &str, b: &str) -> Ordering {
+    match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        _ => a.to_lowercase().cmp(&b.to_lowercase()),
+    }
+}
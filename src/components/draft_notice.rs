@@ -0,0 +1,37 @@
+use yew::prelude::*;
+
+use crate::drafts::Draft;
+use crate::format::format_timestamp;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub draft: Draft,
+    pub onrestore: Callback<()>,
+    pub ondiscard: Callback<()>,
+}
+
+/// Offers to bring back unsaved input from an earlier visit to a form.
+#[function_component(DraftNotice)]
+pub fn draft_notice(props: &Props) -> Html {
+    html! {
+        <div class="alert alert-info d-flex align-items-center gap-2" role="alert">
+            <span class="me-auto">
+                {format!("You have an unsaved draft from {}.", format_timestamp(&props.draft.saved_at))}
+            </span>
+            <button
+                type="button"
+                class="btn btn-sm btn-primary"
+                onclick={props.onrestore.reform(|_: MouseEvent| ())}
+            >
+                {"Restore"}
+            </button>
+            <button
+                type="button"
+                class="btn btn-sm btn-outline-secondary"
+                onclick={props.ondiscard.reform(|_: MouseEvent| ())}
+            >
+                {"Discard"}
+            </button>
+        </div>
+    }
+}
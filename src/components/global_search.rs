@@ -0,0 +1,297 @@
+use gloo_timers::callback::Timeout;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::api::client::ApiClient;
+use crate::api::crates::Crate;
+use crate::api::rustaceans::Rustacean;
+use crate::guard::confirm_leave;
+use crate::hooks::{use_crates, use_rustaceans};
+use crate::Route;
+
+/// Typing pause before the results are refreshed.
+const DEBOUNCE_MS: u32 = 250;
+/// Results listed per entity type.
+const MAX_HITS_PER_GROUP: usize = 5;
+
+#[derive(Clone, Copy, PartialEq)]
+enum HitKind {
+    Crate,
+    Rustacean,
+}
+
+#[derive(Clone, PartialEq)]
+struct SearchHit {
+    kind: HitKind,
+    title: String,
+    detail: String,
+    route: Route,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub client: ApiClient,
+}
+
+/// Search box matching crates by name, code or description and rustaceans by
+/// name or email. Arrow keys move through the results, Enter opens the detail
+/// page, which viewers may see too. Enter pressed before the debounce ran only
+/// searches, so it never opens a result of the previous term.
+#[function_component(GlobalSearch)]
+pub fn global_search(props: &Props) -> Html {
+    // ---
+    let navigator = use_navigator().expect("Navigator not available");
+    let input_handle = use_state(String::new);
+    let term_handle = use_state(String::new);
+    let open_handle = use_state(|| false);
+    let active_handle = use_state(|| 0_usize);
+    let debounce = use_mut_ref(|| None::<Timeout>);
+    // What `SearchResults` currently lists, so Enter knows what to open.
+    let hits_ref = use_mut_ref(Vec::<SearchHit>::new);
+
+    let oninput = {
+        let debounce = debounce.clone();
+        let input_handle = input_handle.clone();
+        let term_handle = term_handle.clone();
+        let open_handle = open_handle.clone();
+        let active_handle = active_handle.clone();
+        Callback::from(move |e: InputEvent| {
+            let Some(input) = e.target_dyn_into::<HtmlInputElement>() else {
+                return;
+            };
+            let value = input.value();
+            input_handle.set(value.clone());
+            open_handle.set(true);
+            active_handle.set(0);
+            let term_handle = term_handle.clone();
+            // Replacing the pending timeout cancels it.
+            *debounce.borrow_mut() =
+                Some(Timeout::new(DEBOUNCE_MS, move || term_handle.set(value)));
+        })
+    };
+
+    let onselect = {
+        let input_handle = input_handle.clone();
+        let term_handle = term_handle.clone();
+        let open_handle = open_handle.clone();
+        Callback::from(move |hit: SearchHit| {
+            if !confirm_leave() {
+                return;
+            }
+            navigator.push(&hit.route);
+            input_handle.set(String::new());
+            term_handle.set(String::new());
+            open_handle.set(false);
+        })
+    };
+
+    let onkeydown = {
+        let input_handle = input_handle.clone();
+        let term_handle = term_handle.clone();
+        let open_handle = open_handle.clone();
+        let active_handle = active_handle.clone();
+        let hits_ref = hits_ref.clone();
+        let onselect = onselect.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            let count = hits_ref.borrow().len();
+            match e.key().as_str() {
+                "ArrowDown" => {
+                    e.prevent_default();
+                    open_handle.set(true);
+                    if count > 0 {
+                        active_handle.set((*active_handle + 1).min(count - 1));
+                    }
+                }
+                "ArrowUp" => {
+                    e.prevent_default();
+                    active_handle.set(active_handle.saturating_sub(1));
+                }
+                "Enter" => {
+                    e.prevent_default();
+                    // The listed results are still those of an earlier term:
+                    // search for what was typed now and let the next Enter pick.
+                    if *input_handle != *term_handle {
+                        debounce.borrow_mut().take();
+                        term_handle.set((*input_handle).clone());
+                        return;
+                    }
+                    let hit = hits_ref.borrow().get(*active_handle).cloned();
+                    if let Some(hit) = hit {
+                        onselect.emit(hit);
+                    }
+                }
+                "Escape" => open_handle.set(false),
+                _ => {}
+            }
+        })
+    };
+
+    let onfocus = {
+        let open_handle = open_handle.clone();
+        Callback::from(move |_: FocusEvent| open_handle.set(true))
+    };
+    let onblur = {
+        let open_handle = open_handle.clone();
+        Callback::from(move |_: FocusEvent| open_handle.set(false))
+    };
+
+    let onhits = Callback::from(move |hits: Vec<SearchHit>| *hits_ref.borrow_mut() = hits);
+
+    let show_results = *open_handle && !term_handle.trim().is_empty();
+
+    html! {
+        <div class="global-search position-relative">
+            <input
+                type="search"
+                class="form-control"
+                name="search"
+                placeholder="Search crates and rustaceans"
+                autocomplete="off"
+                role="combobox"
+                aria-expanded={show_results.to_string()}
+                value={(*input_handle).clone()}
+                oninput={oninput}
+                onkeydown={onkeydown}
+                onfocus={onfocus}
+                onblur={onblur}
+            />
+            if show_results {
+                <SearchResults
+                    client={props.client.clone()}
+                    term={(*term_handle).clone()}
+                    active={*active_handle}
+                    onhits={onhits}
+                    onselect={onselect}
+                />
+            }
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct ResultsProps {
+    client: ApiClient,
+    term: AttrValue,
+    active: usize,
+    onhits: Callback<Vec<SearchHit>>,
+    onselect: Callback<SearchHit>,
+}
+
+/// Mounted only while there is something to search for, so the full lists are
+/// not fetched on every page.
+#[function_component(SearchResults)]
+fn search_results(props: &ResultsProps) -> Html {
+    // ---
+    let crates = use_crates(&props.client);
+    let rustaceans = use_rustaceans(&props.client);
+
+    let hits = search(
+        &props.term,
+        crates
+            .data
+            .as_deref()
+            .map(Vec::as_slice)
+            .unwrap_or_default(),
+        rustaceans
+            .data
+            .as_deref()
+            .map(Vec::as_slice)
+            .unwrap_or_default(),
+    );
+
+    {
+        let onhits = props.onhits.clone();
+        use_effect_with(hits.clone(), move |hits| {
+            onhits.emit(hits.clone());
+            move || onhits.emit(Vec::new())
+        });
+    }
+
+    let loading = crates.data.is_none() || rustaceans.data.is_none();
+    let error = crates.error.clone().or(rustaceans.error.clone());
+
+    let item = |(index, hit): (usize, &SearchHit)| {
+        let classes = if index == props.active {
+            classes!("list-group-item", "list-group-item-action", "active")
+        } else {
+            classes!("list-group-item", "list-group-item-action")
+        };
+        let onselect = props.onselect.clone();
+        let selected = hit.clone();
+        // Mousedown rather than click: the input's blur would close the list first.
+        let onmousedown = Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            onselect.emit(selected.clone());
+        });
+        html! {
+            <li class={classes} role="option" onmousedown={onmousedown}>
+                <div>{hit.title.clone()}</div>
+                <small>{hit.detail.clone()}</small>
+            </li>
+        }
+    };
+
+    let group = |kind: HitKind, heading: &'static str| {
+        let group_hits: Vec<(usize, &SearchHit)> = hits
+            .iter()
+            .enumerate()
+            .filter(|(_, hit)| hit.kind == kind)
+            .collect();
+        if group_hits.is_empty() {
+            return html! {};
+        }
+        html! {
+            <>
+                <li class="list-group-item list-group-item-light small text-muted">{heading}</li>
+                { group_hits.into_iter().map(item).collect::<Html>() }
+            </>
+        }
+    };
+
+    html! {
+        <ul class="list-group position-absolute w-100 shadow-sm global-search-results" role="listbox">
+            if let Some(error) = error {
+                <li class="list-group-item text-danger">{error.to_string()}</li>
+            } else if hits.is_empty() && loading {
+                <li class="list-group-item text-muted">{"Searching ..."}</li>
+            } else if hits.is_empty() {
+                <li class="list-group-item text-muted">{"No matches"}</li>
+            }
+            { group(HitKind::Crate, "Crates") }
+            { group(HitKind::Rustacean, "Rustaceans") }
+        </ul>
+    }
+}
+
+/// Case-insensitive substring match, crates first then rustaceans.
+fn search(term: &str, crates: &[Crate], rustaceans: &[Rustacean]) -> Vec<SearchHit> {
+    let needle = term.trim().to_lowercase();
+    let matches = |field: &str| field.to_lowercase().contains(&needle);
+
+    let crate_hits = crates
+        .iter()
+        .filter(|c| {
+            matches(&c.name) || matches(&c.code) || c.description.as_deref().is_some_and(matches)
+        })
+        .take(MAX_HITS_PER_GROUP)
+        .map(|c| SearchHit {
+            kind: HitKind::Crate,
+            title: c.name.clone(),
+            detail: format!("{} v{}", c.code, c.version),
+            route: Route::CratesShow { id: c.id },
+        });
+
+    let rustacean_hits = rustaceans
+        .iter()
+        .filter(|r| matches(&r.name) || matches(&r.email))
+        .take(MAX_HITS_PER_GROUP)
+        .map(|r| SearchHit {
+            kind: HitKind::Rustacean,
+            title: r.name.clone(),
+            detail: r.email.clone(),
+            route: Route::RustaceansShow { id: r.id },
+        });
+
+    crate_hits.chain(rustacean_hits).collect()
+}
@@ -1,38 +1,76 @@
+use crate::api::error::ApiError;
+use crate::api::user::api_logout;
 use crate::components::button::Button;
-use crate::{
-    contexts::{CurrentUserActions, CurrentUserContext, CurrentUserDispatchActions},
-    Route,
-};
+use crate::components::global_search::GlobalSearch;
+use crate::components::login_redirect::LoginRedirect;
+use crate::components::session_expiry::SessionExpiryWarning;
+use crate::contexts::{CurrentUserActions, CurrentUserContext, CurrentUserDispatchActions};
+use crate::guard::confirm_leave;
+use yew::platform::spawn_local;
 use yew::prelude::*;
-use yew_router::prelude::*;
 
 #[function_component(Header)]
 pub fn header() -> Html {
     let current_user_ctx =
         use_context::<CurrentUserContext>().expect("Current user context is missing");
+    let logging_out_handle = use_state(|| false);
 
-    match &current_user_ctx.user {
-        Some(user) => {
-            let cloned_user_ctx = current_user_ctx.clone();
-            let onclick = Callback::from(move |e: MouseEvent| {
-                e.prevent_default();
-                cloned_user_ctx.dispatch(CurrentUserDispatchActions {
-                    action_type: CurrentUserActions::LoginFail,
-                    login_response: None,
-                    me_response: None,
-                });
-            });
+    match (&current_user_ctx.user, current_user_ctx.client()) {
+        (Some(user), Some(client)) => {
+            // The session is revoked on the backend first; local state is
+            // cleared whatever the outcome, so logging out always works.
+            let onclick = {
+                let cloned_user_ctx = current_user_ctx.clone();
+                let logging_out_handle = logging_out_handle.clone();
+                let client = client.clone();
+                Callback::from(move |e: MouseEvent| {
+                    e.prevent_default();
+                    if *logging_out_handle || !confirm_leave() {
+                        return;
+                    }
+                    logging_out_handle.set(true);
+                    let cloned_user_ctx = cloned_user_ctx.clone();
+                    let client = client.clone();
+                    spawn_local(async move {
+                        let warning = match api_logout(&client).await {
+                            // Already expired or revoked: nothing left to end.
+                            Ok(()) | Err(ApiError::Unauthorized) => None,
+                            Err(e) => {
+                                log::warn!("Server-side logout failed: {e}");
+                                Some(format!(
+                                    "You are logged out in this browser, but the server could not end the session: {e}"
+                                ))
+                            }
+                        };
+                        cloned_user_ctx.dispatch(CurrentUserDispatchActions {
+                            action_type: CurrentUserActions::Logout { warning },
+                            session: None,
+                            me_response: None,
+                        });
+                    });
+                })
+            };
+            let logging_out = *logging_out_handle;
             html! {
-                <div class="text-end">
-                    <p>
-                        <span class="pe-1">{"Welcome "}{user.username.clone()}</span>
-                        <Button button_type="danger" onclick={onclick} label="Logout" />
-                    </p>
-                </div>
+                <>
+                    <div class="d-flex justify-content-between align-items-start gap-3 mb-3">
+                        <GlobalSearch client={client} />
+                        <p class="text-end text-nowrap mb-0">
+                            <span class="pe-1">{"Welcome "}{user.username.clone()}</span>
+                            <Button
+                                button_type="danger"
+                                onclick={onclick}
+                                label={if logging_out { "Logging out ..." } else { "Logout" }}
+                                disabled={logging_out}
+                            />
+                        </p>
+                    </div>
+                    <SessionExpiryWarning />
+                </>
             }
         }
-        None => html! {
-            <Redirect<Route> to={Route::Login} />
+        _ => html! {
+            <LoginRedirect />
         },
     }
 }
@@ -6,23 +6,36 @@ pub struct Props {
     pub input_type: AttrValue,
     pub name: AttrValue,
     pub value: AttrValue,
-    pub onchange: Callback<Event>,
+    /// Emitted on every keystroke.
+    pub oninput: Callback<InputEvent>,
+    #[prop_or_default]
+    pub onblur: Callback<FocusEvent>,
+    /// Shown under the field, which is then marked invalid.
+    #[prop_or_default]
+    pub error: Option<AttrValue>,
+    /// Prepended to the element id, for a second form with the same fields.
+    #[prop_or_default]
+    pub id_prefix: AttrValue,
 }
 
 #[function_component(Input)]
 pub fn input(props: &Props) -> Html {
-    let html_id = format!("edit-{}", props.name);
+    let html_id = format!("{}edit-{}", props.id_prefix, props.name);
     html! {
         <>
             <label for={html_id.clone()}>{props.label.clone()}</label>
             <input
                 id={html_id}
-                class="form-control"
+                class={classes!("form-control", props.error.is_some().then_some("is-invalid"))}
                 type={props.input_type.clone()}
                 name={props.name.clone()}
                 value={props.value.clone()}
-                onchange={props.onchange.clone()}
+                oninput={props.oninput.clone()}
+                onblur={props.onblur.clone()}
             />
+            if let Some(error) = &props.error {
+                <div class="invalid-feedback">{error.clone()}</div>
+            }
         </>
     }
 }
@@ -4,73 +4,92 @@ use web_sys::HtmlInputElement;
 use yew::{platform::spawn_local, prelude::*};
 use yew_router::prelude::*;
 
+use crate::api::client::ApiClient;
+use crate::api::error::ApiError;
 use crate::api::user::{api_login, api_me, LoginResponse, MeResponse};
 use crate::components::alert::Alert;
 use crate::components::input::Input;
+use crate::components::login_redirect::{push_next, LoginQuery};
 use crate::contexts::{CurrentUserActions, CurrentUserContext, CurrentUserDispatchActions};
-use crate::Route;
+use crate::form::{required, use_form, Field};
+use crate::session::Session;
 
 async fn login(
+    client: ApiClient,
     username: String,
     password: String,
-) -> Result<(LoginResponse, MeResponse), gloo_net::Error> {
-    let login_reponse = api_login(username, password).await?;
-    let me_response = api_me(&login_reponse.token).await?;
+) -> Result<(LoginResponse, MeResponse), ApiError> {
+    let login_reponse = api_login(&client, username, password).await?;
+    let me_response = api_me(&client.with_token(&login_reponse.token)).await?;
     Ok((login_reponse, me_response))
 }
 
 #[function_component(LoginForm)]
 pub fn login_form() -> Html {
     let navigator = use_navigator().expect("Navigator not available");
+    let next = use_location()
+        .and_then(|location| location.query::<LoginQuery>().ok())
+        .and_then(|query| query.next);
     let current_user_ctx =
         use_context::<CurrentUserContext>().expect("Current user context is missing");
 
-    let username_handle = use_state(String::default);
-    let username = (*username_handle).clone();
-    let password_handle = use_state(String::default);
-    let password = (*password_handle).clone();
+    let form = use_form(|| {
+        vec![
+            Field::new("username", "").validate(required()),
+            Field::new("password", "").validate(required()),
+        ]
+    });
+    let remember_handle = use_state(|| false);
     let error_message_handle = use_state(String::default);
     let error_message = (*error_message_handle).clone();
 
-    let username_changed = Callback::from(move |e: Event| {
-        let target = e.target_dyn_into::<HtmlInputElement>();
-        if let Some(input) = target {
-            username_handle.set(input.value());
-        }
-    });
-
-    let password_changed = Callback::from(move |e: Event| {
-        let target = e.target_dyn_into::<HtmlInputElement>();
-        if let Some(input) = target {
-            password_handle.set(input.value());
-        }
-    });
-
-    let username_ = username.clone();
-    let password_ = password.clone();
+    let form_ = form.clone();
+    let remember = *remember_handle;
     let onsubmit = Callback::from(move |e: SubmitEvent| {
         e.prevent_default();
 
-        let username_ = username_.clone();
-        let password_ = password_.clone();
+        if !form_.begin_submit() {
+            return;
+        }
+
+        let form_ = form_.clone();
+        let username_ = form_.value("username");
+        let password_ = form_.value("password");
         let error_handle_ = error_message_handle.clone();
         let navigator_ = navigator.clone();
+        let next_ = next.clone();
         let user_ctx_ = current_user_ctx.clone();
+        let client = user_ctx_.anonymous_client();
         spawn_local(async move {
-            match login(username_.clone(), password_.clone()).await {
-                Ok(responses) => {
+            match login(client, username_, password_).await {
+                Ok((login_response, me_response)) => {
                     user_ctx_.dispatch(CurrentUserDispatchActions {
                         action_type: CurrentUserActions::LoginSuccess,
-                        login_response: Some(responses.0),
-                        me_response: Some(responses.1),
+                        session: Some(Session::start(login_response.token, remember)),
+                        me_response: Some(me_response),
                     });
-                    navigator_.push(&Route::Home);
+                    push_next(&navigator_, next_.as_deref());
                 }
-                Err(e) => error_handle_.set(e.to_string()),
+                // The login endpoint answers bad credentials with 401, which
+                // would otherwise read as an expired session.
+                Err(ApiError::Unauthorized) => {
+                    error_handle_.set("Invalid username or password".to_string());
+                    form_.end_submit();
+                }
+                Err(e) => error_handle_.set(form_.fail_submit(&e).unwrap_or_default()),
             }
         });
     });
 
+    let remember_changed = {
+        let remember_handle = remember_handle.clone();
+        Callback::from(move |e: Event| {
+            if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                remember_handle.set(input.checked());
+            }
+        })
+    };
+
     {
         debug!("Login form rendered");
     }
@@ -85,8 +104,10 @@ pub fn login_form() -> Html {
                     input_type="text"
                     name="username"
                     label="Username"
-                    value={username}
-                    onchange={username_changed}
+                    value={form.value("username")}
+                    oninput={form.oninput("username")}
+                    onblur={form.onblur("username")}
+                    error={form.error("username")}
                 />
             </div>
             <div class="mb-3">
@@ -94,11 +115,33 @@ pub fn login_form() -> Html {
                     input_type="password"
                     name="password"
                     label="Password"
-                    value={password}
-                    onchange={password_changed}
+                    value={form.value("password")}
+                    oninput={form.oninput("password")}
+                    onblur={form.onblur("password")}
+                    error={form.error("password")}
+                />
+            </div>
+            <div class="form-check mb-3">
+                <input
+                    id="login-remember"
+                    type="checkbox"
+                    class="form-check-input"
+                    name="remember"
+                    checked={remember}
+                    onchange={remember_changed}
                 />
+                <label class="form-check-label" for="login-remember">
+                    {"Remember me"}
+                </label>
+                <div class="form-text">
+                    {"Stay logged in across tabs and browser restarts. Leave unchecked on shared computers."}
+                </div>
             </div>
-            <Button button_type="primary" label="Login" />
+            <Button
+                button_type="primary"
+                label={if form.is_submitting() { "Logging in ..." } else { "Login" }}
+                disabled={form.is_submitting()}
+            />
         </form>
     }
 }
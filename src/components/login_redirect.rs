@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+use yew_router::history::{BrowserHistory, History};
+use yew_router::prelude::*;
+
+use crate::components::splash::Splash;
+use crate::contexts::CurrentUserContext;
+use crate::Route;
+
+/// Query string of the login page: `/login?next=/crates/3/edit`.
+#[derive(Serialize, Deserialize, Default, PartialEq, Clone)]
+pub struct LoginQuery {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+}
+
+/// Sends the user to the login page, remembering where they were so that
+/// `LoginForm` can bring them back afterwards. While a stored session is being
+/// restored it shows the splash screen instead and waits.
+#[function_component(LoginRedirect)]
+pub fn login_redirect() -> Html {
+    let navigator = use_navigator().expect("Navigator not available");
+    let location = use_location().expect("Location not available");
+    let current_user_ctx =
+        use_context::<CurrentUserContext>().expect("Current user context is missing");
+    let restoring = current_user_ctx.is_restoring();
+
+    use_effect_with(restoring, move |restoring| {
+        if *restoring {
+            return;
+        }
+        let next = format!("{}{}", location.path(), location.query_str());
+        let query = LoginQuery {
+            next: is_safe_next(&next).then_some(next),
+        };
+        if let Err(e) = navigator.replace_with_query(&Route::Login, &query) {
+            log::warn!("Failed to redirect to login: {e}");
+            navigator.replace(&Route::Login);
+        }
+    });
+
+    if restoring {
+        return html! { <Splash /> };
+    }
+    html! {}
+}
+
+/// Only same-origin app paths are honoured; anything else (`//evil.com`,
+/// `https://...`, the login page itself) falls back to `Route::Home`. Browsers
+/// drop tabs and newlines from URLs, so `/<tab>/evil.com` is rejected too.
+fn is_safe_next(next: &str) -> bool {
+    next.starts_with('/')
+        && !next.contains(char::is_control)
+        && !next.starts_with("//")
+        && !next.starts_with("/\\")
+        && next != "/"
+        && !next.starts_with(&Route::Login.to_path())
+}
+
+/// Navigate to the `next` path carried by the login page, or home.
+pub fn push_next(navigator: &Navigator, next: Option<&str>) {
+    match next.filter(|next| is_safe_next(next)) {
+        // `Navigator` only pushes typed routes; the shared browser history lets
+        // us keep the original query string intact.
+        Some(next) => BrowserHistory::new().push(next),
+        None => navigator.push(&Route::Home),
+    }
+}
+
+/// Like `push_next`, without leaving the login page in the history.
+pub fn replace_next(navigator: &Navigator, next: Option<&str>) {
+    match next.filter(|next| is_safe_next(next)) {
+        Some(next) => BrowserHistory::new().replace(next),
+        None => navigator.replace(&Route::Home),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn app_paths_are_safe() {
+        assert!(is_safe_next("/crates"));
+        assert!(is_safe_next("/crates/3/edit"));
+        assert!(is_safe_next("/rustaceans?page=2&limit=50"));
+    }
+
+    #[test]
+    fn other_origins_are_rejected() {
+        for next in [
+            "//evil.com",
+            "//evil.com/crates",
+            "/\\evil.com",
+            "https://evil.com",
+            "javascript:alert(1)",
+            "evil.com",
+            "/\t/evil.com",
+            "/\n/evil.com",
+            "",
+        ] {
+            assert!(!is_safe_next(next), "{next:?}");
+        }
+    }
+
+    #[test]
+    fn login_and_home_are_rejected() {
+        assert!(!is_safe_next("/"));
+        assert!(!is_safe_next("/login"));
+        assert!(!is_safe_next("/login?next=/crates"));
+        assert!(!is_safe_next("/login?next=//evil.com"));
+    }
+}
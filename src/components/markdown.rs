@@ -0,0 +1,46 @@
+use yew::prelude::*;
+
+use crate::markdown::{to_html, truncate};
+
+/// Characters of a description shown in tables before "more".
+pub const EXCERPT_LENGTH: usize = 120;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub source: AttrValue,
+    /// Show only about this many characters, with a "more" link for the rest.
+    #[prop_or_default]
+    pub limit: Option<usize>,
+}
+
+/// Sanitized Markdown, e.g. a crate description.
+#[function_component(Markdown)]
+pub fn markdown(props: &Props) -> Html {
+    // ---
+    let expanded_handle = use_state(|| false);
+    let excerpt = props.limit.and_then(|limit| truncate(&props.source, limit));
+    let shown = match &excerpt {
+        Some(excerpt) if !*expanded_handle => excerpt.as_str(),
+        _ => props.source.as_str(),
+    };
+    let rendered = use_memo(shown.to_string(), |shown| to_html(shown));
+
+    let toggle = {
+        let expanded_handle = expanded_handle.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            expanded_handle.set(!*expanded_handle);
+        })
+    };
+
+    html! {
+        <div class="markdown">
+            { Html::from_html_unchecked(AttrValue::from((*rendered).clone())) }
+            if excerpt.is_some() {
+                <a href="#" class="small" onclick={toggle}>
+                    { if *expanded_handle { "less" } else { "more" } }
+                </a>
+            }
+        </div>
+    }
+}
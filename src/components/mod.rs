@@ -1,12 +1,26 @@
 pub mod alert;
+pub mod app_layout;
+pub mod author_picker;
 pub mod button;
 pub mod crate_form;
 pub mod crate_list;
+pub mod data_table;
+pub mod draft_notice;
+pub mod global_search;
 pub mod header;
 pub mod input;
 pub mod login_form;
+pub mod login_redirect;
+pub mod markdown;
+pub mod modal;
+pub mod pagination;
+pub mod query_status;
+pub mod require_auth;
+pub mod require_role;
 pub mod rustacean_form;
 pub mod rustacean_list;
 pub mod select;
+pub mod session_expiry;
 pub mod sidebar;
+pub mod splash;
 pub mod textarea;
@@ -0,0 +1,82 @@
+use web_sys::HtmlElement;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub title: AttrValue,
+    pub onclose: Callback<()>,
+    #[prop_or_default]
+    pub children: Html,
+}
+
+/// Bootstrap modal rendered into `<body>`, so it can be opened from inside a
+/// `<form>` without nesting forms. Closes on the X button, a backdrop click or
+/// Escape; the dialog takes focus when it opens so that Escape works at once.
+#[function_component(Modal)]
+pub fn modal(props: &Props) -> Html {
+    // ---
+    let dialog_ref = use_node_ref();
+    {
+        let dialog_ref = dialog_ref.clone();
+        use_effect_with((), move |_| {
+            if let Some(dialog) = dialog_ref.cast::<HtmlElement>() {
+                let _ = dialog.focus();
+            }
+        });
+    }
+
+    let Some(body) = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.body())
+    else {
+        return html! {};
+    };
+
+    let close = props.onclose.reform(|_: MouseEvent| ());
+    let onkeydown = {
+        let onclose = props.onclose.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() == "Escape" {
+                onclose.emit(());
+            }
+        })
+    };
+    let backdrop_click = props.onclose.reform(|_: MouseEvent| ());
+    // Keep clicks inside the dialog from reaching the backdrop.
+    let dialog_click = Callback::from(|e: MouseEvent| e.stop_propagation());
+
+    create_portal(
+        html! {
+            <>
+                <div
+                    ref={dialog_ref}
+                    class="modal d-block"
+                    tabindex="-1"
+                    role="dialog"
+                    aria-modal="true"
+                    onclick={backdrop_click}
+                    onkeydown={onkeydown}
+                >
+                    <div class="modal-dialog modal-dialog-scrollable" onclick={dialog_click}>
+                        <div class="modal-content">
+                            <div class="modal-header">
+                                <h5 class="modal-title">{props.title.clone()}</h5>
+                                <button
+                                    type="button"
+                                    class="btn-close"
+                                    aria-label="Close"
+                                    onclick={close}
+                                />
+                            </div>
+                            <div class="modal-body">
+                                {props.children.clone()}
+                            </div>
+                        </div>
+                    </div>
+                </div>
+                <div class="modal-backdrop show"></div>
+            </>
+        },
+        body.into(),
+    )
+}
@@ -0,0 +1,126 @@
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+
+use crate::api::page::{PageQuery, MAX_PAGE, PAGE_SIZES};
+use crate::components::select::Select;
+
+/// Page links shown on each side of the current page.
+const PAGE_WINDOW: u32 = 2;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub query: PageQuery,
+    /// Rows on the current page.
+    pub count: usize,
+    #[prop_or_default]
+    pub total: Option<u64>,
+    pub has_next: bool,
+    pub onchange: Callback<PageQuery>,
+}
+
+#[function_component(Pagination)]
+pub fn pagination(props: &Props) -> Html {
+    // ---
+    let query = props.query;
+    let last_page = props
+        .total
+        .map(|total| total.div_ceil(query.limit as u64).clamp(1, MAX_PAGE.into()) as u32);
+
+    let go_to = |page: u32| {
+        let onchange = props.onchange.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            onchange.emit(PageQuery { page, ..query });
+        })
+    };
+
+    let limit_changed = {
+        let onchange = props.onchange.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+                if let Ok(limit) = select.value().parse::<u32>() {
+                    onchange.emit(PageQuery { page: 1, limit });
+                }
+            }
+        })
+    };
+
+    let page_sizes = PAGE_SIZES
+        .iter()
+        .map(|size| {
+            (
+                AttrValue::from(size.to_string()),
+                AttrValue::from(size.to_string()),
+            )
+        })
+        .collect::<Vec<(AttrValue, AttrValue)>>();
+
+    let first_shown = query.page.saturating_sub(PAGE_WINDOW).max(1);
+    let last_shown = match last_page {
+        Some(last) => query.page.saturating_add(PAGE_WINDOW).min(last),
+        None if props.has_next => query.page.saturating_add(1),
+        None => query.page,
+    };
+
+    let page_item = |page: u32| {
+        let classes = if page == query.page {
+            classes!("page-item", "active")
+        } else {
+            classes!("page-item")
+        };
+        html! {
+            <li class={classes}>
+                <a class="page-link" href="#" onclick={go_to(page)}>{page}</a>
+            </li>
+        }
+    };
+
+    let range = if props.count == 0 {
+        "No rows".to_string()
+    } else {
+        let from = query.offset().saturating_add(1);
+        let to = query.offset().saturating_add(props.count as u64);
+        match props.total {
+            Some(total) => format!("Showing {from}–{to} of {total}"),
+            None => format!("Showing {from}–{to}"),
+        }
+    };
+
+    let prev_page = query.page.saturating_sub(1).max(1);
+    let prev_classes = if query.page > 1 {
+        classes!("page-item")
+    } else {
+        classes!("page-item", "disabled")
+    };
+    let next_classes = if props.has_next {
+        classes!("page-item")
+    } else {
+        classes!("page-item", "disabled")
+    };
+
+    html! {
+        <div class="d-flex align-items-center gap-3 flex-wrap">
+            <nav aria-label="Pagination">
+                <ul class="pagination mb-0">
+                    <li class={prev_classes}>
+                        <a class="page-link" href="#" onclick={go_to(prev_page)}>{"«"}</a>
+                    </li>
+                    { (first_shown..=last_shown).map(page_item).collect::<Html>() }
+                    <li class={next_classes}>
+                        <a class="page-link" href="#" onclick={go_to(query.page.saturating_add(1))}>{"»"}</a>
+                    </li>
+                </ul>
+            </nav>
+            <div class="d-flex align-items-center gap-1 text-nowrap">
+                <Select
+                    name="page-size"
+                    label="Rows per page"
+                    value={query.limit.to_string()}
+                    oninput={limit_changed}
+                    options={page_sizes}
+                />
+            </div>
+            <span class="text-muted">{range}</span>
+        </div>
+    }
+}
@@ -0,0 +1,37 @@
+use yew::prelude::*;
+
+use crate::api::error::ApiError;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    #[prop_or_default]
+    pub error: Option<ApiError>,
+    #[prop_or_default]
+    pub loading: bool,
+    pub onretry: Callback<()>,
+}
+
+/// Placeholder shown while a query has no data yet: "Loading ..." or, if the
+/// last fetch failed, the error with a retry button.
+#[function_component(QueryStatus)]
+pub fn query_status(props: &Props) -> Html {
+    // ---
+    match &props.error {
+        Some(error) if !props.loading => {
+            let onretry = props.onretry.clone();
+            let onclick = Callback::from(move |e: MouseEvent| {
+                e.prevent_default();
+                onretry.emit(());
+            });
+            html! {
+                <div class="alert alert-danger d-flex align-items-center" role="alert">
+                    <span class="me-auto">{error.to_string()}</span>
+                    <button type="button" class="btn btn-sm btn-outline-danger" onclick={onclick}>
+                        {"Retry"}
+                    </button>
+                </div>
+            }
+        }
+        _ => html! { <p>{"Loading ..."}</p> },
+    }
+}
@@ -0,0 +1,35 @@
+use yew::prelude::*;
+
+use crate::api::client::ApiClient;
+use crate::components::login_redirect::LoginRedirect;
+use crate::contexts::CurrentUserContext;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub children: Children,
+}
+
+/// Guards protected routes. Logged in users get `children`, with the session's
+/// `ApiClient` provided as context (see `use_api_client`); anyone else is sent
+/// to the login page, which brings them back here afterwards.
+#[function_component(RequireAuth)]
+pub fn require_auth(props: &Props) -> Html {
+    let current_user_ctx =
+        use_context::<CurrentUserContext>().expect("Current user context is missing");
+
+    match (&current_user_ctx.user, current_user_ctx.client()) {
+        (Some(_), Some(client)) => html! {
+            <ContextProvider<ApiClient> context={client}>
+                {props.children.clone()}
+            </ContextProvider<ApiClient>>
+        },
+        // Also covers `Restoring`, for which `LoginRedirect` shows the splash.
+        _ => html! { <LoginRedirect /> },
+    }
+}
+
+/// The logged in user's `ApiClient`. Only available below `RequireAuth`.
+#[hook]
+pub fn use_api_client() -> ApiClient {
+    use_context::<ApiClient>().expect("use_api_client called outside RequireAuth")
+}
@@ -0,0 +1,32 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::api::user::Role;
+use crate::hooks::use_has_role;
+use crate::Route;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub role: Role,
+    pub children: Children,
+}
+
+/// Renders its children only for users holding `role`; everyone else gets an
+/// explanation instead of a form the backend would reject anyway.
+#[function_component(RequireRole)]
+pub fn require_role(props: &Props) -> Html {
+    let allowed = use_has_role(props.role);
+
+    if allowed {
+        return html! { <>{props.children.clone()}</> };
+    }
+    html! {
+        <div class="alert alert-warning" role="alert">
+            {format!(
+                "This page requires the {} role, which your account does not have.",
+                props.role.label()
+            )}
+            <Link<Route> to={Route::Home} classes="alert-link ms-1">{"Back to home"}</Link<Route>>
+        </div>
+    }
+}
@@ -1,18 +1,33 @@
-use web_sys::HtmlInputElement;
 use yew::{platform::spawn_local, prelude::*};
 use yew_router::prelude::*;
 
 use crate::api::rustaceans::{api_rustacean_create, api_rustacean_update, Rustacean};
+use crate::cache::QueryCache;
 use crate::components::alert::Alert;
 use crate::components::button::Button;
+use crate::components::draft_notice::DraftNotice;
 use crate::components::input::Input;
 use crate::contexts::CurrentUserContext;
+use crate::drafts::use_draft;
+use crate::form::{email, max_length, required, use_form, Field};
+use crate::hooks::rustacean_saved;
 use crate::Route;
 
+const MAX_NAME_LENGTH: usize = 128;
+const MAX_EMAIL_LENGTH: usize = 254;
+
 #[derive(Properties, PartialEq)]
 pub struct Props {
     #[prop_or_default]
     pub rustacean: Option<Rustacean>,
+    /// Called with the saved rustacean instead of returning to the list, e.g.
+    /// when the form is shown in a modal.
+    #[prop_or_default]
+    pub onsaved: Option<Callback<Rustacean>>,
+    /// Prepended to the input ids and the draft key when the form is shown next
+    /// to another one, e.g. in a modal over `CrateForm`.
+    #[prop_or_default]
+    pub id_prefix: AttrValue,
 }
 
 #[function_component(RustaceanForm)]
@@ -21,74 +36,87 @@ pub fn rustacean_form(props: &Props) -> Html {
     let navigator = use_navigator().expect("Navigator not available");
     let current_user_ctx =
         use_context::<CurrentUserContext>().expect("Current user context is missing");
+    let cache = use_context::<QueryCache>().expect("Query cache context is missing");
 
-    let name_handle = use_state(|| {
-        if let Some(r) = &props.rustacean {
-            return r.name.clone();
-        }
-        String::default()
+    let rustacean = props.rustacean.clone();
+    let form = use_form(move || {
+        let initial =
+            |value: fn(&Rustacean) -> String| rustacean.as_ref().map(value).unwrap_or_default();
+        vec![
+            Field::new("name", initial(|r| r.name.clone()))
+                .validate(required())
+                .validate(max_length(MAX_NAME_LENGTH)),
+            Field::new("email", initial(|r| r.email.clone()))
+                .validate(required())
+                .validate(email())
+                .validate(max_length(MAX_EMAIL_LENGTH)),
+        ]
     });
-    let name = (*name_handle).clone();
-    let email_handle = use_state(|| {
-        if let Some(r) = &props.rustacean {
-            return r.email.clone();
-        }
-        String::default()
-    });
-    let email = (*email_handle).clone();
+    let draft_key = match &props.rustacean {
+        Some(existing) => format!("{}rustacean-{}", props.id_prefix, existing.id),
+        None => format!("{}rustacean-new", props.id_prefix),
+    };
+    let draft = use_draft(draft_key.clone(), &form);
     let error_message_handle = use_state(String::default);
     let error_message = (*error_message_handle).clone();
 
-    let name_changed = Callback::from(move |e: Event| {
-        let target = e.target_dyn_into::<HtmlInputElement>();
-        if let Some(input) = target {
-            name_handle.set(input.value());
-        }
-    });
-
-    let email_changed = Callback::from(move |e: Event| {
-        let target = e.target_dyn_into::<HtmlInputElement>();
-        if let Some(input) = target {
-            email_handle.set(input.value());
-        }
-    });
-
-    let name_ = name.clone();
-    let email_ = email.clone();
+    let discard_draft = draft.discard.clone();
+    let form_ = form.clone();
     let rustacean_ = props.rustacean.clone();
+    let onsaved = props.onsaved.clone();
     let onsubmit = Callback::from(move |e: SubmitEvent| {
         e.prevent_default();
+        // Inside a modal this form may sit within another form's subtree.
+        e.stop_propagation();
+
+        if !form_.begin_submit() {
+            return;
+        }
 
-        let name_ = name_.clone();
-        let email_ = email_.clone();
+        let form_ = form_.clone();
+        let name_ = form_.value("name");
+        let email_ = form_.value("email");
         let rustacean_ = rustacean_.clone();
         let error_handle_ = error_message_handle.clone();
         let navigator_ = navigator.clone();
-        let user_ctx_ = current_user_ctx.clone();
+        let cache_ = cache.clone();
+        let discard_draft_ = discard_draft.clone();
+        let onsaved_ = onsaved.clone();
 
-        match &user_ctx_.token {
-            Some(token) => {
-                let token = token.clone();
+        match current_user_ctx.client() {
+            Some(client) => {
                 spawn_local(async move {
-                    if let Some(rustacean) = rustacean_ {
-                        match api_rustacean_update(&token, rustacean.id, name_, email_).await {
-                            Ok(_) => navigator_.push(&Route::Rustaceans),
-                            Err(e) => error_handle_.set(e.to_string()),
+                    let result = match rustacean_ {
+                        Some(rustacean) => {
+                            api_rustacean_update(&client, rustacean.id, name_, email_).await
                         }
-                    } else {
-                        match api_rustacean_create(&token, name_, email_).await {
-                            Ok(_) => navigator_.push(&Route::Rustaceans),
-                            Err(e) => error_handle_.set(e.to_string()),
+                        None => api_rustacean_create(&client, name_, email_).await,
+                    };
+                    match result {
+                        Ok(rustacean) => {
+                            rustacean_saved(&cache_, &rustacean);
+                            discard_draft_.emit(());
+                            match onsaved_ {
+                                Some(onsaved) => onsaved.emit(rustacean),
+                                None => navigator_.push(&Route::Rustaceans),
+                            }
                         }
+                        Err(e) => error_handle_.set(form_.fail_submit(&e).unwrap_or_default()),
                     }
                 });
             }
-            None => error_handle_.set("Session expired. Please login again".to_string()),
+            None => {
+                error_handle_.set("Session expired. Please login again".to_string());
+                form_.end_submit();
+            }
         }
     });
 
     html! {
         <form onsubmit={onsubmit}>
+            if let Some(pending) = draft.pending.clone() {
+                <DraftNotice draft={pending} onrestore={draft.restore} ondiscard={draft.discard} />
+            }
             if !error_message.is_empty() {
                 <Alert alert_type={"danger"} message={error_message} />
             }
@@ -97,8 +125,11 @@ pub fn rustacean_form(props: &Props) -> Html {
                     input_type="text"
                     name="name"
                     label="Name"
-                    value={name}
-                    onchange={name_changed}
+                    id_prefix={props.id_prefix.clone()}
+                    value={form.value("name")}
+                    oninput={form.oninput("name")}
+                    onblur={form.onblur("name")}
+                    error={form.error("name")}
                 />
             </div>
             <div class="mb-3">
@@ -106,11 +137,21 @@ pub fn rustacean_form(props: &Props) -> Html {
                     input_type="email"
                     name="email"
                     label="E-mail"
-                    value={email}
-                    onchange={email_changed}
+                    id_prefix={props.id_prefix.clone()}
+                    value={form.value("email")}
+                    oninput={form.oninput("email")}
+                    onblur={form.onblur("email")}
+                    error={form.error("email")}
                 />
             </div>
-            <Button button_type="primary" label="Save" />
+            <Button
+                button_type="primary"
+                label={if form.is_submitting() { "Saving ..." } else { "Save" }}
+                disabled={form.is_submitting()}
+            />
+            if form.is_dirty() && !form.is_submitting() {
+                <span class="ms-2 text-muted">{"Unsaved changes"}</span>
+            }
         </form>
     }
 }
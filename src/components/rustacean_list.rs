@@ -1,62 +1,104 @@
-use crate::hooks::use_rustaceans;
+use std::rc::Rc;
+
+use crate::api::client::ApiClient;
+use crate::api::page::PageQuery;
+use crate::api::rustaceans::Rustacean;
+use crate::api::user::Role;
+use crate::components::app_layout::PageActions;
+use crate::components::data_table::{Column, DataTable};
+use crate::components::pagination::Pagination;
+use crate::components::query_status::QueryStatus;
+use crate::format::format_timestamp;
+use crate::hooks::{use_has_role, use_rustaceans_page};
 use crate::Route;
 use yew::prelude::*;
 use yew_router::prelude::*;
 
 #[derive(Properties, PartialEq)]
 pub struct Props {
-    pub token: AttrValue,
+    pub client: ApiClient,
 }
 
 #[function_component(RustaceanList)]
-pub fn rustacean_list(props: &Props) -> HtmlResult {
-    let rustaceans = use_rustaceans(&props.token)?;
+pub fn rustacean_list(props: &Props) -> Html {
+    let navigator = use_navigator().expect("Navigator not available");
+    let can_edit = use_has_role(Role::Editor);
+    let page_query = use_location()
+        .and_then(|location| location.query::<PageQuery>().ok())
+        .unwrap_or_default()
+        .normalized();
+    let query = use_rustaceans_page(&props.client, page_query);
+    let Some(page) = query.data.clone() else {
+        return html! {
+            <QueryStatus
+                error={query.error.clone()}
+                loading={query.loading}
+                onretry={query.refetch.clone()}
+            />
+        };
+    };
 
-    Ok(html! {
-        <>
-            <p>
-                <Link<Route> to={Route::RustaceansAdd}>
-                    {"+ Add new rustacean"}
-                </Link<Route>>
-            </p>
-            <table class="table">
-                <thead>
-                    <th>{"ID"}</th>
-                    <th>{"Name"}</th>
-                    <th>{"Email"}</th>
-                    <th>{"Created at"}</th>
-                    <th>{"Operations"}</th>
-                </thead>
-                <tbody>
-                {
-                    rustaceans.into_iter().map(|rustacean| {
-                        html! {
-                            <tr>
-                                <td>{rustacean.id}</td>
-                                <td>{rustacean.name}</td>
-                                <td>{rustacean.email}</td>
-                                <td>{rustacean.created_at}</td>
-                                <td>
-                                    <Link<Route>
-                                        to={Route::RustaceansEdit { id: rustacean.id }}
-                                        classes="link-secondary"
-                                    >
-                                        {"edit"}
-                                    </Link<Route>>
-                                    <span class="mx-1">{"/"}</span>
-                                    <Link<Route>
-                                        to={Route::RustaceansDelete { id: rustacean.id }}
-                                        classes="link-danger"
-                                    >
-                                        {"delete"}
-                                    </Link<Route>>
-                                </td>
-                            </tr>
-                        }
-                    }).collect::<Html>()
+    let onpagechange = Callback::from(move |page_query: PageQuery| {
+        if let Err(e) = navigator.push_with_query(&Route::Rustaceans, &page_query) {
+            log::warn!("Failed to change page: {e}");
+        }
+    });
+
+    let mut columns = vec![
+        Column::new("id", "ID", |r: &Rustacean| r.id.to_string()),
+        Column::new("name", "Name", |r: &Rustacean| r.name.clone()).render(|r: &Rustacean| {
+            html! {
+                <Link<Route> to={Route::RustaceansShow { id: r.id }}>{r.name.clone()}</Link<Route>>
+            }
+        }),
+        Column::new("email", "Email", |r: &Rustacean| r.email.clone()),
+        Column::new("created_at", "Created at", |r: &Rustacean| {
+            format_timestamp(&r.created_at)
+        }),
+    ];
+    if can_edit {
+        columns.push(Column::actions(
+            "operations",
+            "Operations",
+            |r: &Rustacean| {
+                html! {
+                    <>
+                        <Link<Route>
+                            to={Route::RustaceansEdit { id: r.id }}
+                            classes="link-secondary"
+                        >
+                            {"edit"}
+                        </Link<Route>>
+                        <span class="mx-1">{"/"}</span>
+                        <Link<Route>
+                            to={Route::RustaceansDelete { id: r.id }}
+                            classes="link-danger"
+                        >
+                            {"delete"}
+                        </Link<Route>>
+                    </>
                 }
-                </tbody>
-            </table>
+            },
+        ));
+    }
+
+    html! {
+        <>
+            if can_edit {
+                <PageActions>
+                    <Link<Route> to={Route::RustaceansAdd} classes="btn btn-sm btn-primary">
+                        {"+ Add new rustacean"}
+                    </Link<Route>>
+                </PageActions>
+            }
+            <DataTable<Rustacean> id="rustaceans" columns={columns} rows={Rc::new(page.items.clone())} paged=true />
+            <Pagination
+                query={page.query}
+                count={page.items.len()}
+                total={page.total}
+                has_next={page.has_next()}
+                onchange={onpagechange}
+            />
         </>
-    })
+    }
 }
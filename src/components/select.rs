@@ -3,10 +3,21 @@ use yew::prelude::*;
 #[derive(Properties, PartialEq)]
 pub struct Props {
     pub label: AttrValue,
+    /// `(value, label)` pairs.
     pub options: Vec<(AttrValue, AttrValue)>,
     pub name: AttrValue,
+    /// The option with this value is shown selected; none is when it is empty.
     pub value: AttrValue,
-    pub onchange: Callback<Event>,
+    /// Emitted as soon as another option is picked.
+    pub oninput: Callback<InputEvent>,
+    #[prop_or_default]
+    pub onblur: Callback<FocusEvent>,
+    /// Shown, and not selectable, while `value` is empty.
+    #[prop_or_default]
+    pub placeholder: Option<AttrValue>,
+    /// Shown under the field, which is then marked invalid.
+    #[prop_or_default]
+    pub error: Option<AttrValue>,
 }
 
 #[function_component(Select)]
@@ -17,18 +28,29 @@ pub fn select(props: &Props) -> Html {
             <label for={html_id.clone()}>{props.label.clone()}</label>
             <select
                 id={html_id}
-                class="form-control"
+                class={classes!("form-select", props.error.is_some().then_some("is-invalid"))}
                 name={props.name.clone()}
-                onchange={props.onchange.clone()}
+                oninput={props.oninput.clone()}
+                onblur={props.onblur.clone()}
             >
+                if let Some(placeholder) = &props.placeholder {
+                    <option value="" disabled=true selected={props.value.is_empty()}>
+                        {placeholder.clone()}
+                    </option>
+                }
                 {
-                    props.options.iter().map(|option| {
+                    props.options.iter().map(|(value, label)| {
                         html! {
-                            <option value={option.0.clone()}>{option.1.clone()}</option>
+                            <option value={value.clone()} selected={*value == props.value}>
+                                {label.clone()}
+                            </option>
                         }
                     }).collect::<Html>()
                 }
             </select>
+            if let Some(error) = &props.error {
+                <div class="invalid-feedback">{error.clone()}</div>
+            }
         </>
     }
 }
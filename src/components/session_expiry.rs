@@ -0,0 +1,60 @@
+use gloo_timers::callback::Interval;
+use yew::prelude::*;
+
+use crate::contexts::{CurrentUserActions, CurrentUserContext, CurrentUserDispatchActions};
+use crate::guard::confirm_leave;
+
+/// How long before the session expires the warning appears.
+const WARN_BEFORE_MS: f64 = 5.0 * 60.0 * 1000.0;
+const TICK_MS: u32 = 15_000;
+
+/// Warns that the session is about to expire, with a countdown and a way to log
+/// in again right away. That ends the session in this tab only and keeps any
+/// draft. Renders nothing until the last few minutes.
+#[function_component(SessionExpiryWarning)]
+pub fn session_expiry_warning() -> Html {
+    // ---
+    let current_user_ctx =
+        use_context::<CurrentUserContext>().expect("Current user context is missing");
+    let force_update = use_force_update();
+
+    use_effect_with((), move |_| {
+        let interval = Interval::new(TICK_MS, move || force_update.force_update());
+        move || drop(interval)
+    });
+
+    let Some(expires_at) = current_user_ctx.expires_at else {
+        return html! {};
+    };
+    let remaining = expires_at - js_sys::Date::now();
+    if remaining > WARN_BEFORE_MS || remaining <= 0.0 {
+        return html! {};
+    }
+
+    let onclick = Callback::from(move |e: MouseEvent| {
+        e.prevent_default();
+        if !confirm_leave() {
+            return;
+        }
+        current_user_ctx.dispatch(CurrentUserDispatchActions {
+            action_type: CurrentUserActions::SessionEnded,
+            session: None,
+            me_response: None,
+        });
+    });
+    let minutes = (remaining / 60_000.0).ceil() as u32;
+
+    html! {
+        <div class="alert alert-warning d-flex align-items-center gap-2" role="alert">
+            <span class="me-auto">
+                {format!(
+                    "Your session expires in {minutes} minute{}. Save your work and log in again to continue.",
+                    if minutes == 1 { "" } else { "s" }
+                )}
+            </span>
+            <button type="button" class="btn btn-sm btn-outline-dark" onclick={onclick}>
+                {"Log in again"}
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,14 @@
+use yew::prelude::*;
+
+/// Covers the page while the stored session is being restored, so neither the
+/// login page nor a half-rendered protected page flashes by.
+#[function_component(Splash)]
+pub fn splash() -> Html {
+    html! {
+        <div class="splash" role="status" aria-live="polite">
+            <img src="/yew-logo.svg" alt="logo" />
+            <div class="spinner-border text-secondary" aria-hidden="true"></div>
+            <span class="visually-hidden">{"Restoring your session ..."}</span>
+        </div>
+    }
+}
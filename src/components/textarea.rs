@@ -1,26 +1,119 @@
 use yew::prelude::*;
 
+use crate::components::markdown::Markdown;
+
 #[derive(Properties, PartialEq)]
 pub struct Props {
     pub label: AttrValue,
     pub name: AttrValue,
     pub value: AttrValue,
-    pub onchange: Callback<Event>,
+    /// Emitted on every keystroke.
+    pub oninput: Callback<InputEvent>,
+    #[prop_or_default]
+    pub onblur: Callback<FocusEvent>,
+    /// Shown under the field, which is then marked invalid.
+    #[prop_or_default]
+    pub error: Option<AttrValue>,
+    /// Adds Write / Split / Preview tabs rendering the value as Markdown.
+    #[prop_or_default]
+    pub markdown: bool,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Write,
+    Split,
+    Preview,
+}
+
+impl Mode {
+    const ALL: [Mode; 3] = [Mode::Write, Mode::Split, Mode::Preview];
+
+    fn label(self) -> &'static str {
+        match self {
+            Mode::Write => "Write",
+            Mode::Split => "Split",
+            Mode::Preview => "Preview",
+        }
+    }
 }
 
 #[function_component(Textarea)]
 pub fn textarea(props: &Props) -> Html {
     let html_id = format!("edit-{}", props.name);
+    let mode_handle = use_state(|| Mode::Write);
+    let mode = if props.markdown {
+        *mode_handle
+    } else {
+        Mode::Write
+    };
+
+    let tab = |tab_mode: Mode| {
+        let mode_handle = mode_handle.clone();
+        let onclick = Callback::from(move |_: MouseEvent| mode_handle.set(tab_mode));
+        let classes = if tab_mode == mode {
+            classes!("nav-link", "active")
+        } else {
+            classes!("nav-link")
+        };
+        html! {
+            <li class="nav-item">
+                <button type="button" class={classes} onclick={onclick}>
+                    {tab_mode.label()}
+                </button>
+            </li>
+        }
+    };
+
+    let editor = html! {
+        <textarea
+            id={html_id.clone()}
+            class={classes!("form-control", props.error.is_some().then_some("is-invalid"))}
+            name={props.name.clone()}
+            value={props.value.clone()}
+            rows={if props.markdown { "8" } else { "3" }}
+            oninput={props.oninput.clone()}
+            onblur={props.onblur.clone()}
+        />
+    };
+    let preview = html! {
+        <div class="form-control markdown-preview">
+            if props.value.trim().is_empty() {
+                <span class="text-muted">{"Nothing to preview"}</span>
+            } else {
+                <Markdown source={props.value.clone()} />
+            }
+        </div>
+    };
+    let error = match &props.error {
+        Some(error) => html! { <div class="invalid-feedback d-block">{error.clone()}</div> },
+        None => html! {},
+    };
+
     html! {
         <>
-            <label for={html_id.clone()}>{props.label.clone()}</label>
-            <textarea
-                id={html_id}
-                class="form-control"
-                name={props.name.clone()}
-                value={props.value.clone()}
-                onchange={props.onchange.clone()}
-            />
+            <label for={html_id}>{props.label.clone()}</label>
+            if props.markdown {
+                <ul class="nav nav-tabs mb-1">
+                    { Mode::ALL.iter().map(|m| tab(*m)).collect::<Html>() }
+                </ul>
+            }
+            {
+                match mode {
+                    Mode::Write => editor,
+                    Mode::Split => html! {
+                        <div class="row g-2">
+                            <div class="col-md-6">{editor}</div>
+                            <div class="col-md-6">{preview}</div>
+                        </div>
+                    },
+                    Mode::Preview => preview,
+                }
+            }
+            {error}
+            if props.markdown {
+                <div class="form-text">{"Markdown is supported: code blocks, links, lists, tables."}</div>
+            }
         </>
     }
 }
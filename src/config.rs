@@ -0,0 +1,90 @@
+use gloo_net::http::Request;
+use serde::Deserialize;
+
+/// Served alongside `index.html`; lets a deployment point the app at its backend
+/// without rebuilding.
+const CONFIG_URL: &str = "/config.json";
+
+/// `<meta name="cr8s-api-base" content="...">` in `index.html`, checked when no
+/// `config.json` is served.
+const API_BASE_META: &str = "cr8s-api-base";
+
+const DEFAULT_API_BASE: &str = "http://127.0.0.1:8000/cr8s";
+
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub struct AppConfig {
+    pub api_base: String,
+}
+
+impl AppConfig {
+    /// Resolve the runtime configuration, in order of precedence:
+    /// `config.json`, the `cr8s-api-base` meta tag, then the compile-time
+    /// `CR8S_API_BASE` fallback.
+    pub async fn load() -> Self {
+        // ---
+        if let Some(config) = fetch_config_json().await {
+            log::info!("Loaded {CONFIG_URL}, api_base={}", config.api_base);
+            return config;
+        }
+        if let Some(api_base) = meta_api_base() {
+            log::info!("Using api_base={api_base} from <meta name=\"{API_BASE_META}\">");
+            return Self::with_api_base(api_base);
+        }
+        let config = Self::fallback();
+        log::info!("Using compile-time api_base={}", config.api_base);
+        config
+    }
+
+    pub fn fallback() -> Self {
+        Self::with_api_base(option_env!("CR8S_API_BASE").unwrap_or(DEFAULT_API_BASE))
+    }
+
+    fn with_api_base(api_base: impl Into<String>) -> Self {
+        let api_base: String = api_base.into();
+        Self {
+            api_base: api_base.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+async fn fetch_config_json() -> Option<AppConfig> {
+    // ---
+    let response = match Request::get(CONFIG_URL).send().await {
+        Ok(response) if response.ok() => response,
+        Ok(response) => {
+            log::debug!("{CONFIG_URL} not served (HTTP {})", response.status());
+            return None;
+        }
+        Err(e) => {
+            log::debug!("{CONFIG_URL} not reachable: {e}");
+            return None;
+        }
+    };
+
+    // Dev servers answer unknown paths with index.html, so a parse failure
+    // simply means there is no config file.
+    match response.json::<AppConfig>().await {
+        Ok(config) if !config.api_base.trim().is_empty() => {
+            Some(AppConfig::with_api_base(config.api_base))
+        }
+        Ok(_) => None,
+        Err(e) => {
+            log::debug!("{CONFIG_URL} is not a valid config: {e}");
+            None
+        }
+    }
+}
+
+fn meta_api_base() -> Option<String> {
+    let document = web_sys::window()?.document()?;
+    let meta = document
+        .query_selector(&format!("meta[name=\"{API_BASE_META}\"]"))
+        .ok()??;
+    let content = meta.get_attribute("content")?;
+    let content = content.trim();
+    if content.is_empty() {
+        None
+    } else {
+        Some(content.to_string())
+    }
+}
@@ -1,18 +1,84 @@
 use std::rc::Rc;
 
-use gloo_storage::{SessionStorage, Storage};
+use gloo_timers::callback::Timeout;
 use yew::platform::spawn_local;
 use yew::prelude::*;
-use yew::{Reducible, UseReducerHandle};
+use yew::{Reducible, UseReducerDispatcher, UseReducerHandle};
 
-use crate::api::user::{api_me, LoginResponse, MeResponse, User};
+use crate::api::client::ApiClient;
+use crate::api::error::ApiError;
+use crate::api::user::{api_me, MeResponse, Role, User};
+use crate::cache::QueryCache;
+use crate::config::AppConfig;
+use crate::releases;
+use crate::session::{self, use_logout_elsewhere, Session};
+
+pub type AppConfigContext = Rc<AppConfig>;
 
 pub type CurrentUserContext = UseReducerHandle<CurrentUser>;
 
-#[derive(PartialEq, Default)]
+/// Where the app stands on who is logged in.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AuthState {
+    /// A stored session is being checked against the backend; guards wait for
+    /// the outcome instead of sending the user to the login page.
+    Restoring,
+    Authenticated,
+    Anonymous,
+}
+
+#[derive(PartialEq)]
 pub struct CurrentUser {
+    pub auth: AuthState,
     pub user: Option<User>,
     pub token: Option<String>,
+    /// When the session expires, in milliseconds since the epoch.
+    pub expires_at: Option<f64>,
+    /// Shown on the login page after a logout the backend could not complete.
+    pub logout_warning: Option<String>,
+    api: ApiClient,
+}
+
+impl CurrentUser {
+    fn new(api: ApiClient) -> Self {
+        Self {
+            auth: AuthState::Anonymous,
+            user: None,
+            token: None,
+            expires_at: None,
+            logout_warning: None,
+            api,
+        }
+    }
+
+    pub fn is_restoring(&self) -> bool {
+        self.auth == AuthState::Restoring
+    }
+
+    /// Client carrying the session token, or `None` when logged out.
+    pub fn client(&self) -> Option<ApiClient> {
+        self.token
+            .as_deref()
+            .map(|token| self.api.with_token(token))
+    }
+
+    /// Whether the logged in user holds `role`, directly or through a higher one.
+    pub fn has_role(&self, role: Role) -> bool {
+        self.user.as_ref().is_some_and(|user| user.has_role(role))
+    }
+
+    /// Part of the local storage keys for data that belongs to the logged in
+    /// user on this backend, e.g. release history; `None` when logged out.
+    pub fn storage_scope(&self) -> Option<String> {
+        self.user
+            .as_ref()
+            .map(|user| format!("{}_{}", self.api.base(), user.id))
+    }
+
+    /// Client for unauthenticated endpoints such as `/login`.
+    pub fn anonymous_client(&self) -> ApiClient {
+        self.api.clone()
+    }
 }
 
 impl Reducible for CurrentUser {
@@ -23,39 +89,64 @@ impl Reducible for CurrentUser {
         match action.action_type {
             CurrentUserActions::LoginSuccess => {
                 let me_resp = action.me_response.expect("Missing login response");
-                let login_resp = action.login_response.expect("Missing login response");
-                let _ = SessionStorage::set("cr8s_token", login_resp.token.clone());
+                let session = action.session.expect("Missing session");
+                session::save(&session);
                 Self {
+                    auth: AuthState::Authenticated,
                     user: Some(User {
                         id: me_resp.id,
                         username: me_resp.username,
                         created_at: me_resp.created_at,
+                        roles: me_resp.roles,
                     }),
-                    token: Some(login_resp.token),
+                    token: Some(session.token),
+                    expires_at: Some(session.expires_at),
+                    logout_warning: None,
+                    api: self.api.clone(),
                 }
                 .into()
             }
-            CurrentUserActions::LoginFail => {
-                SessionStorage::clear();
+            CurrentUserActions::Logout { warning } => {
+                session::clear_everywhere();
+                releases::clear_all();
                 Self {
-                    user: None,
-                    token: None,
+                    logout_warning: warning,
+                    ..Self::new(self.api.clone())
                 }
                 .into()
             }
+            CurrentUserActions::SessionEnded => {
+                let token = action.session.map(|s| s.token).or(self.token.clone());
+                if let Some(token) = token {
+                    session::clear(&token);
+                }
+                Self::new(self.api.clone()).into()
+            }
+            CurrentUserActions::RestoreFailed => Self::new(self.api.clone()).into(),
         }
     }
 }
 
 pub struct CurrentUserDispatchActions {
     pub action_type: CurrentUserActions,
-    pub login_response: Option<LoginResponse>,
+    pub session: Option<Session>,
     pub me_response: Option<MeResponse>,
 }
 
 pub enum CurrentUserActions {
     LoginSuccess,
-    LoginFail,
+    /// The user asked to log out: here and in every other tab. `warning`
+    /// reports a failure to revoke the session on the backend.
+    Logout {
+        warning: Option<String>,
+    },
+    /// The session expired, the backend rejected it, another tab logged out or
+    /// the user chose to log in again before it expires; only this tab's state and the stored copy of its session are cleared.
+    /// `session` names the stored session when the state does not hold it yet.
+    SessionEnded,
+    /// The stored session could not be checked, e.g. the backend is down. It
+    /// is kept for the next attempt.
+    RestoreFailed,
 }
 
 #[derive(Properties, PartialEq)]
@@ -65,27 +156,153 @@ pub struct Props {
 
 #[function_component(CurrentUserProvider)]
 pub fn curent_user_provider(props: &Props) -> Html {
-    let user = use_reducer(CurrentUser::default);
+    let config = use_context::<AppConfigContext>().expect("App config context is missing");
 
-    if user.user.is_none() {
-        if let Ok(token) = SessionStorage::get::<String>("cr8s_token") {
-            let cloned_user = user.clone();
-            spawn_local(async move {
-                match api_me(&token).await {
-                    Ok(me_response) => cloned_user.dispatch(CurrentUserDispatchActions {
-                        action_type: CurrentUserActions::LoginSuccess,
-                        login_response: Some(LoginResponse { token }),
-                        me_response: Some(me_response),
-                    }),
-                    Err(_) => SessionStorage::clear(),
+    // Read once: while a stored session exists the app starts out `Restoring`.
+    let stored = use_memo((), |_| session::load());
+
+    // The client is created inside the reducer's initial state, before a
+    // dispatcher exists, so the 401 handler reaches it through this slot.
+    let dispatcher_slot = use_mut_ref(|| None::<UseReducerDispatcher<CurrentUser>>);
+    let user = use_reducer({
+        let dispatcher_slot = dispatcher_slot.clone();
+        let config = config.clone();
+        let restoring = stored.is_some();
+        move || {
+            let on_unauthorized = Callback::from(move |_| {
+                log::info!("Session rejected by the backend, logging out");
+                if let Some(dispatcher) = dispatcher_slot.borrow().as_ref() {
+                    dispatcher.dispatch(CurrentUserDispatchActions {
+                        action_type: CurrentUserActions::SessionEnded,
+                        session: None,
+                        me_response: None,
+                    });
                 }
             });
+            let api = ApiClient::new(&config.api_base).on_unauthorized(on_unauthorized);
+            CurrentUser {
+                auth: if restoring {
+                    AuthState::Restoring
+                } else {
+                    AuthState::Anonymous
+                },
+                ..CurrentUser::new(api)
+            }
         }
+    });
+    dispatcher_slot
+        .borrow_mut()
+        .get_or_insert_with(|| user.dispatcher());
+
+    // A single `/me` request on mount. Its client has no 401 handler: a stale
+    // token found here must not log other tabs out.
+    {
+        let dispatcher = user.dispatcher();
+        use_effect_with((), move |_| {
+            if let Some(stored) = (*stored).clone() {
+                let client = ApiClient::new(&config.api_base).with_token(&stored.token);
+                spawn_local(async move {
+                    let action = match api_me(&client).await {
+                        Ok(me_response) => CurrentUserDispatchActions {
+                            action_type: CurrentUserActions::LoginSuccess,
+                            session: Some(stored),
+                            me_response: Some(me_response),
+                        },
+                        Err(ApiError::Unauthorized) => CurrentUserDispatchActions {
+                            action_type: CurrentUserActions::SessionEnded,
+                            session: Some(stored),
+                            me_response: None,
+                        },
+                        Err(e) => {
+                            log::warn!("Could not restore the session: {e}");
+                            CurrentUserDispatchActions {
+                                action_type: CurrentUserActions::RestoreFailed,
+                                session: None,
+                                me_response: None,
+                            }
+                        }
+                    };
+                    dispatcher.dispatch(action);
+                });
+            }
+        });
     }
 
+    let end_session = {
+        let dispatcher = user.dispatcher();
+        Callback::from(move |_| {
+            dispatcher.dispatch(CurrentUserDispatchActions {
+                action_type: CurrentUserActions::SessionEnded,
+                session: None,
+                me_response: None,
+            })
+        })
+    };
+    use_logout_elsewhere(user.token.clone(), end_session.clone());
+
+    // Log out when the session runs out rather than on the next failing request.
+    use_effect_with(user.expires_at.map(f64::to_bits), move |expires_at| {
+        let timeout = expires_at.map(|bits| {
+            let remaining = f64::from_bits(bits) - js_sys::Date::now();
+            // `setTimeout` fires at once for delays beyond a signed 32-bit int.
+            let delay = remaining.clamp(0.0, i32::MAX as f64) as u32;
+            Timeout::new(delay, move || {
+                log::info!("Session expired, logging out");
+                end_session.emit(());
+            })
+        });
+        move || drop(timeout)
+    });
+
     html! {
         <ContextProvider<CurrentUserContext> context={user}>
             {props.children.clone()}
         </ContextProvider<CurrentUserContext>>
     }
 }
+
+/// Resolves the runtime `AppConfig` once at startup and provides it to the rest of
+/// the app. Children are not rendered until the config is known, so no `ApiClient`
+/// is ever built against the wrong backend.
+#[function_component(AppConfigProvider)]
+pub fn app_config_provider(props: &Props) -> Html {
+    let config = use_state(|| None::<AppConfigContext>);
+
+    {
+        let config = config.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                config.set(Some(Rc::new(AppConfig::load().await)));
+            });
+        });
+    }
+
+    match &*config {
+        Some(config) => html! {
+            <ContextProvider<AppConfigContext> context={config.clone()}>
+                {props.children.clone()}
+            </ContextProvider<AppConfigContext>>
+        },
+        None => html! { <p>{"Loading ..."}</p> },
+    }
+}
+
+/// Provides the shared `QueryCache`. It is emptied whenever the session token
+/// changes, so one user never sees data fetched for another.
+#[function_component(QueryCacheProvider)]
+pub fn query_cache_provider(props: &Props) -> Html {
+    let current_user_ctx =
+        use_context::<CurrentUserContext>().expect("Current user context is missing");
+    let cache = use_memo((), |_| QueryCache::default());
+
+    {
+        let cache = cache.clone();
+        use_effect_with(current_user_ctx.token.clone(), move |_| cache.clear());
+    }
+
+    html! {
+        <ContextProvider<QueryCache> context={(*cache).clone()}>
+            {props.children.clone()}
+        </ContextProvider<QueryCache>>
+    }
+}
@@ -0,0 +1,122 @@
+use gloo_storage::{LocalStorage, Storage};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+use crate::form::FormHandle;
+use crate::guard::use_leave_guard;
+use crate::hooks::use_storage_scope;
+
+/// Unsaved form values, kept in local storage until the form is saved or the
+/// draft is discarded.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct Draft {
+    /// ISO 8601 time of the last change.
+    pub saved_at: String,
+    pub values: IndexMap<String, String>,
+}
+
+const KEY_PREFIX: &str = "cr8s_draft_";
+
+/// `scope` is `CurrentUser::storage_scope`: a draft is only offered to the user
+/// who left it, on the backend it was meant for, and survives their logout.
+fn storage_key(scope: &str, key: &str) -> String {
+    format!("{KEY_PREFIX}{scope}_{key}")
+}
+
+fn load(storage_key: &str) -> Option<Draft> {
+    LocalStorage::get(storage_key).ok()
+}
+
+fn discard(storage_key: &str) {
+    LocalStorage::delete(storage_key);
+}
+
+fn save(storage_key: &str, values: &[(&'static str, String)]) {
+    let draft = Draft {
+        saved_at: js_sys::Date::new_0().to_iso_string().into(),
+        values: values
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect(),
+    };
+    if let Err(e) = LocalStorage::set(storage_key, &draft) {
+        log::warn!("Could not save draft: {e}");
+    }
+}
+
+/// What `use_draft` hands back: a draft left by an earlier visit, until the
+/// user restores or discards it.
+pub struct DraftHandle {
+    pub pending: Option<Draft>,
+    pub restore: Callback<()>,
+    /// Drop the stored draft; also call it once the form was saved.
+    pub discard: Callback<()>,
+}
+
+/// Keep `form`'s values as a draft under `key` while they are unsaved, guard
+/// against leaving the page, and offer to restore a draft from an earlier visit.
+/// Nothing is stored while logged out.
+#[hook]
+pub fn use_draft(key: String, form: &FormHandle) -> DraftHandle {
+    // ---
+    let key = use_storage_scope().map(|scope| storage_key(&scope, &key));
+    let pending_handle = {
+        let key = key.clone();
+        let initial = form.values();
+        use_state(move || {
+            key.as_deref().and_then(load).filter(|draft| {
+                initial
+                    .iter()
+                    .any(|(name, value)| draft.values.get(*name).is_some_and(|v| v != value))
+            })
+        })
+    };
+
+    use_leave_guard(form.is_dirty() && !form.is_submitting());
+
+    {
+        // An earlier draft is not overwritten until the user decided about it.
+        let pending = pending_handle.is_some();
+        let key = key.clone();
+        use_effect_with(
+            (form.values(), form.is_dirty(), pending),
+            move |(values, dirty, pending)| {
+                let Some(key) = key.filter(|_| !*pending) else {
+                    return;
+                };
+                if *dirty {
+                    save(&key, values);
+                } else {
+                    discard(&key);
+                }
+            },
+        );
+    }
+
+    let restore = {
+        let pending_handle = pending_handle.clone();
+        let form = form.clone();
+        Callback::from(move |_| {
+            if let Some(draft) = &*pending_handle {
+                form.restore(&draft.values);
+            }
+            pending_handle.set(None);
+        })
+    };
+    let discard_draft = {
+        let pending_handle = pending_handle.clone();
+        Callback::from(move |_| {
+            if let Some(key) = &key {
+                discard(key);
+            }
+            pending_handle.set(None);
+        })
+    };
+
+    DraftHandle {
+        pending: (*pending_handle).clone(),
+        restore,
+        discard: discard_draft,
+    }
+}
@@ -0,0 +1,462 @@
+use std::rc::Rc;
+
+use indexmap::IndexMap;
+use web_sys::{HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
+use yew::prelude::*;
+
+use crate::api::error::ApiError;
+use crate::semver::Version;
+
+/// Checks one field value, returning the message to show when it is invalid.
+pub type Validator = Rc<dyn Fn(&str) -> Option<String>>;
+
+/// Crate codes: letters, digits, `-` and `_`, starting with a letter or digit.
+const CRATE_CODE_HINT: &str =
+    "Use letters, digits, \"-\" and \"_\", starting with a letter or digit";
+
+pub fn required() -> Validator {
+    Rc::new(|value| {
+        value
+            .trim()
+            .is_empty()
+            .then(|| "This field is required".to_string())
+    })
+}
+
+pub fn max_length(max: usize) -> Validator {
+    Rc::new(move |value| {
+        (value.chars().count() > max).then(|| format!("Must be at most {max} characters"))
+    })
+}
+
+// The validators below accept an empty value; combine them with `required()`.
+
+pub fn email() -> Validator {
+    Rc::new(|value| {
+        let value = value.trim();
+        if value.is_empty() {
+            return None;
+        }
+        let valid = match value.split_once('@') {
+            Some((local, domain)) => {
+                !local.is_empty()
+                    && !domain.contains('@')
+                    && domain.contains('.')
+                    && !domain.starts_with('.')
+                    && !domain.ends_with('.')
+                    && !value.chars().any(char::is_whitespace)
+            }
+            None => false,
+        };
+        (!valid).then(|| "Enter a valid e-mail address".to_string())
+    })
+}
+
+pub fn semver() -> Validator {
+    Rc::new(|value| {
+        if value.trim().is_empty() {
+            return None;
+        }
+        Version::parse(value).err()
+    })
+}
+
+pub fn crate_code() -> Validator {
+    Rc::new(|value| {
+        let value = value.trim();
+        let first = value.chars().next()?;
+        let valid = first.is_ascii_alphanumeric()
+            && value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        (!valid).then(|| CRATE_CODE_HINT.to_string())
+    })
+}
+
+/// A form field: its name, starting value and validators, run in order.
+pub struct Field {
+    name: &'static str,
+    initial: String,
+    validators: Vec<Validator>,
+    server_names: Vec<&'static str>,
+    conflict_message: Option<&'static str>,
+}
+
+impl Field {
+    pub fn new(name: &'static str, initial: impl Into<String>) -> Self {
+        Self {
+            name,
+            initial: initial.into(),
+            validators: Vec::new(),
+            server_names: Vec::new(),
+            conflict_message: None,
+        }
+    }
+
+    pub fn validate(mut self, validator: Validator) -> Self {
+        self.validators.push(validator);
+        self
+    }
+
+    /// Another name the backend uses for this field in validation errors.
+    pub fn server_name(mut self, name: &'static str) -> Self {
+        self.server_names.push(name);
+        self
+    }
+
+    /// Show a 409 Conflict from the backend on this field, e.g. a code that
+    /// must be unique; `fallback` is used when the response has no message.
+    pub fn on_conflict(mut self, fallback: &'static str) -> Self {
+        self.conflict_message = Some(fallback);
+        self
+    }
+
+    fn answers_to(&self, server_name: &str) -> bool {
+        self.name == server_name || self.server_names.contains(&server_name)
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct FieldState {
+    name: &'static str,
+    initial: String,
+    value: String,
+    touched: bool,
+    /// Set from a rejected submit; cleared as soon as the value is edited.
+    server_error: Option<String>,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct FormState {
+    fields: Vec<FieldState>,
+    submitting: bool,
+}
+
+pub enum FormAction {
+    Set(&'static str, String),
+    Touch(&'static str),
+    /// Reveal every error, e.g. when a submit is attempted.
+    TouchAll,
+    Submitting(bool),
+    /// Messages from the backend for the named fields; ends the submit.
+    ServerErrors(Vec<(&'static str, String)>),
+}
+
+impl Reducible for FormState {
+    type Action = FormAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut next = (*self).clone();
+        match action {
+            FormAction::Set(name, value) => {
+                if let Some(field) = next.fields.iter_mut().find(|f| f.name == name) {
+                    if field.value != value {
+                        field.server_error = None;
+                    }
+                    field.value = value;
+                }
+            }
+            FormAction::Touch(name) => {
+                if let Some(field) = next.fields.iter_mut().find(|f| f.name == name) {
+                    field.touched = true;
+                }
+            }
+            FormAction::TouchAll => next.fields.iter_mut().for_each(|f| f.touched = true),
+            FormAction::Submitting(submitting) => next.submitting = submitting,
+            FormAction::ServerErrors(errors) => {
+                next.submitting = false;
+                for (name, message) in errors {
+                    if let Some(field) = next.fields.iter_mut().find(|f| f.name == name) {
+                        field.touched = true;
+                        field.server_error = Some(message);
+                    }
+                }
+            }
+        }
+        if next == *self {
+            self
+        } else {
+            Rc::new(next)
+        }
+    }
+}
+
+/// Handle returned by `use_form`. Errors of a field are only reported once it
+/// was touched (left, or set from code), so a fresh form is not covered in red.
+#[derive(Clone)]
+pub struct FormHandle {
+    state: UseReducerHandle<FormState>,
+    fields: Rc<Vec<Field>>,
+}
+
+impl FormHandle {
+    pub fn value(&self, name: &str) -> String {
+        self.field_state(name)
+            .map(|field| field.value.clone())
+            .unwrap_or_default()
+    }
+
+    /// The backend's message from the last submit, else the first failing
+    /// validator's message once the field was touched.
+    pub fn error(&self, name: &str) -> Option<AttrValue> {
+        let field = self.field_state(name)?;
+        if let Some(server_error) = &field.server_error {
+            return Some(AttrValue::from(server_error.clone()));
+        }
+        if !field.touched {
+            return None;
+        }
+        self.validation_error(name, &field.value)
+            .map(AttrValue::from)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.state
+            .fields
+            .iter()
+            .all(|field| self.validation_error(field.name, &field.value).is_none())
+    }
+
+    /// Every field's current value, in declaration order.
+    pub fn values(&self) -> Vec<(&'static str, String)> {
+        self.state
+            .fields
+            .iter()
+            .map(|field| (field.name, field.value.clone()))
+            .collect()
+    }
+
+    /// Set several values at once, e.g. from a saved draft. Unknown names are
+    /// ignored.
+    pub fn restore(&self, values: &IndexMap<String, String>) {
+        for field in self.fields.iter() {
+            if let Some(value) = values.get(field.name) {
+                self.state
+                    .dispatch(FormAction::Set(field.name, value.clone()));
+            }
+        }
+    }
+
+    /// Whether any value differs from what the form started with.
+    pub fn is_dirty(&self) -> bool {
+        self.state
+            .fields
+            .iter()
+            .any(|field| field.value != field.initial)
+    }
+
+    pub fn is_submitting(&self) -> bool {
+        self.state.submitting
+    }
+
+    /// Set a value from code, e.g. a "bump version" button.
+    pub fn set(&self, name: &'static str, value: impl Into<String>) {
+        self.state.dispatch(FormAction::Set(name, value.into()));
+        self.state.dispatch(FormAction::Touch(name));
+    }
+
+    /// Input handler for `Input`, `Select` or `Textarea`.
+    pub fn oninput(&self, name: &'static str) -> Callback<InputEvent> {
+        let state = self.state.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(value) = event_value(&e) {
+                state.dispatch(FormAction::Set(name, value));
+            }
+        })
+    }
+
+    pub fn onblur(&self, name: &'static str) -> Callback<FocusEvent> {
+        let state = self.state.clone();
+        Callback::from(move |_: FocusEvent| state.dispatch(FormAction::Touch(name)))
+    }
+
+    /// Reveal all errors and, if the form is valid and not already submitting,
+    /// mark it submitting and return `true`. The submit button stays enabled
+    /// while the form is invalid so that this is how the user learns why.
+    pub fn begin_submit(&self) -> bool {
+        // Decided before dispatching: the handle keeps showing the state of
+        // the last render, which `TouchAll` does not change the validity of.
+        let ready = self.is_valid() && !self.is_submitting();
+        self.state.dispatch(FormAction::TouchAll);
+        if ready {
+            self.state.dispatch(FormAction::Submitting(true));
+        }
+        ready
+    }
+
+    /// Call once the request started by `begin_submit` failed, so the form can
+    /// be submitted again.
+    pub fn end_submit(&self) {
+        self.state.dispatch(FormAction::Submitting(false));
+    }
+
+    /// End a failed submit. Field errors from a validation response, and a
+    /// conflict if a field handles those (`Field::on_conflict`), are shown on
+    /// the matching fields; whatever is left over is returned for the
+    /// form-level alert.
+    pub fn fail_submit(&self, error: &ApiError) -> Option<String> {
+        if let ApiError::Conflict(message) = error {
+            let field = self.fields.iter().find_map(|field| {
+                field
+                    .conflict_message
+                    .map(|fallback| (field.name, fallback))
+            });
+            if let Some((name, fallback)) = field {
+                let message = message.clone().unwrap_or_else(|| fallback.to_string());
+                self.state
+                    .dispatch(FormAction::ServerErrors(vec![(name, message)]));
+                return None;
+            }
+        }
+        let ApiError::Validation { message, fields } = error else {
+            self.end_submit();
+            return Some(error.to_string());
+        };
+
+        let mut matched = Vec::new();
+        let mut unmatched = Vec::new();
+        for (server_name, messages) in fields {
+            let message = messages.join(", ");
+            match self.fields.iter().find(|f| f.answers_to(server_name)) {
+                Some(field) => matched.push((field.name, message)),
+                None => unmatched.push(format!("{server_name}: {message}")),
+            }
+        }
+        let any_matched = !matched.is_empty();
+        self.state.dispatch(FormAction::ServerErrors(matched));
+
+        match (message, unmatched.is_empty()) {
+            // The fields say it all; a generic "Unprocessable Entity" adds nothing.
+            (_, true) if any_matched => None,
+            (None, true) => Some(error.to_string()),
+            (Some(message), true) => Some(message.clone()),
+            (message, false) => Some(format!(
+                "{}: {}",
+                message.as_deref().unwrap_or("Validation failed"),
+                unmatched.join("; ")
+            )),
+        }
+    }
+
+    fn field_state(&self, name: &str) -> Option<&FieldState> {
+        self.state.fields.iter().find(|field| field.name == name)
+    }
+
+    fn validation_error(&self, name: &str, value: &str) -> Option<String> {
+        self.fields
+            .iter()
+            .find(|field| field.name == name)?
+            .validators
+            .iter()
+            .find_map(|validator| validator(value))
+    }
+}
+
+/// Form state with per-field validation, touched/dirty tracking and a
+/// submitting flag. `init` runs once, on the first render.
+#[hook]
+pub fn use_form<F>(init: F) -> FormHandle
+where
+    F: FnOnce() -> Vec<Field>,
+{
+    // ---
+    let fields = use_memo((), move |_| init());
+    let state = {
+        let fields = fields.clone();
+        use_reducer(move || FormState {
+            fields: fields
+                .iter()
+                .map(|field| FieldState {
+                    name: field.name,
+                    initial: field.initial.clone(),
+                    value: field.initial.clone(),
+                    touched: false,
+                    server_error: None,
+                })
+                .collect(),
+            submitting: false,
+        })
+    };
+    FormHandle { state, fields }
+}
+
+fn event_value(e: &Event) -> Option<String> {
+    if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+        return Some(input.value());
+    }
+    if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+        return Some(select.value());
+    }
+    e.target_dyn_into::<HtmlTextAreaElement>()
+        .map(|textarea| textarea.value())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn required_rejects_blank_values() {
+        let validator = required();
+        assert!(validator("").is_some());
+        assert!(validator("  \t").is_some());
+        assert_eq!(validator("serde"), None);
+    }
+
+    #[test]
+    fn max_length_counts_characters_not_bytes() {
+        let validator = max_length(3);
+        assert_eq!(validator("abc"), None);
+        assert_eq!(validator("äöü"), None);
+        assert_eq!(
+            validator("abcd").as_deref(),
+            Some("Must be at most 3 characters")
+        );
+    }
+
+    #[test]
+    fn email_accepts_plain_addresses_only() {
+        let validator = email();
+        for valid in ["", "ferris@example.com", "  a.b+c@sub.example.org  "] {
+            assert_eq!(validator(valid), None, "{valid:?}");
+        }
+        for invalid in [
+            "ferris",
+            "@example.com",
+            "ferris@example",
+            "ferris@.example.com",
+            "ferris@example.com.",
+            "fer ris@example.com",
+            "ferris@ex@ample.com",
+        ] {
+            assert!(validator(invalid).is_some(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn semver_reports_the_parse_error() {
+        let validator = semver();
+        assert_eq!(validator(""), None);
+        assert_eq!(validator("1.4.0-rc.1+build.2"), None);
+        assert_eq!(
+            validator("1.4").as_deref(),
+            Some("Expected MAJOR.MINOR.PATCH, e.g. 1.4.0")
+        );
+        assert!(validator("1.04.0").is_some());
+    }
+
+    #[test]
+    fn crate_code_allows_dashes_and_underscores_after_the_first_character() {
+        let validator = crate_code();
+        for valid in ["", "serde", "serde_json", "tokio-util", "9lives"] {
+            assert_eq!(validator(valid), None, "{valid:?}");
+        }
+        for invalid in ["-serde", "_serde", "serde json", "serde!", "crä te"] {
+            assert_eq!(
+                validator(invalid).as_deref(),
+                Some(CRATE_CODE_HINT),
+                "{invalid:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,17 @@
+/// Render a backend timestamp (`2025-06-20T14:03:12.123456`, with or without a
+/// zone suffix) as `2025-06-20 14:03`. Anything else is shown unchanged.
+pub fn format_timestamp(value: &str) -> String {
+    let Some((date, time)) = value.split_once('T') else {
+        return value.to_string();
+    };
+    match time.get(..5) {
+        Some(hours_minutes)
+            if date.len() == 10
+                && hours_minutes.as_bytes()[2] == b':'
+                && date.bytes().all(|b| b.is_ascii_digit() || b == b'-') =>
+        {
+            format!("{date} {hours_minutes}")
+        }
+        _ => value.to_string(),
+    }
+}
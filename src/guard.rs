@@ -0,0 +1,131 @@
+use std::cell::{Cell, RefCell};
+
+use gloo_events::{EventListener, EventListenerOptions, EventListenerPhase};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{BeforeUnloadEvent, Element, MouseEvent};
+use yew::prelude::*;
+
+const LEAVE_MESSAGE: &str = "You have unsaved changes. Leave this page? They are kept as a draft for when you come back to the form.";
+
+/// The window listeners shared by every active guard.
+struct Listeners {
+    _beforeunload: EventListener,
+    _click: EventListener,
+    _popstate: EventListener,
+}
+
+thread_local! {
+    /// Mounted forms currently holding unsaved changes.
+    static ACTIVE_GUARDS: Cell<usize> = const { Cell::new(0) };
+    /// Installed while `ACTIVE_GUARDS` is non-zero, so that several dirty forms
+    /// on one page (e.g. one in a modal) still ask only once.
+    static LISTENERS: RefCell<Option<Listeners>> = const { RefCell::new(None) };
+}
+
+/// Ask before discarding unsaved changes. Returns `true` when nothing is
+/// unsaved or the user agreed to leave; call it before navigating from code.
+pub fn confirm_leave() -> bool {
+    if ACTIVE_GUARDS.with(Cell::get) == 0 {
+        return true;
+    }
+    web_sys::window()
+        .and_then(|window| window.confirm_with_message(LEAVE_MESSAGE).ok())
+        .unwrap_or(true)
+}
+
+/// While `active`, ask before following an in-app link, going back or forward,
+/// or closing/reloading the tab. yew-router cannot veto a navigation, so link
+/// clicks and `popstate` are intercepted in the capture phase, before the router
+/// sees them; navigations made from code go through `confirm_leave` instead.
+#[hook]
+pub fn use_leave_guard(active: bool) {
+    use_effect_with(active, |active| {
+        let active = *active;
+        if active {
+            let count = ACTIVE_GUARDS.with(|count| {
+                count.set(count.get() + 1);
+                count.get()
+            });
+            if count == 1 {
+                LISTENERS.with(|listeners| *listeners.borrow_mut() = Some(listen()));
+            }
+        }
+
+        move || {
+            if !active {
+                return;
+            }
+            let count = ACTIVE_GUARDS.with(|count| {
+                count.set(count.get().saturating_sub(1));
+                count.get()
+            });
+            if count == 0 {
+                LISTENERS.with(|listeners| listeners.borrow_mut().take());
+            }
+        }
+    });
+}
+
+fn listen() -> Listeners {
+    let window = web_sys::window().expect("No window");
+    let guarded_url = window.location().href().unwrap_or_default();
+    let capture = || EventListenerOptions {
+        phase: EventListenerPhase::Capture,
+        passive: false,
+    };
+
+    let beforeunload = EventListener::new_with_options(
+        &window,
+        "beforeunload",
+        EventListenerOptions::enable_prevent_default(),
+        |event| {
+            event.prevent_default();
+            if let Some(event) = event.dyn_ref::<BeforeUnloadEvent>() {
+                event.set_return_value(LEAVE_MESSAGE);
+            }
+        },
+    );
+    let click = EventListener::new_with_options(&window, "click", capture(), |event| {
+        if is_link_click(event) && !confirm_leave() {
+            event.prevent_default();
+            event.stop_immediate_propagation();
+        }
+    });
+    // The browser has already moved on when `popstate` fires; put the guarded
+    // page back on top of the history instead.
+    let popstate = EventListener::new_with_options(&window, "popstate", capture(), move |event| {
+        if confirm_leave() {
+            return;
+        }
+        event.stop_immediate_propagation();
+        if let Some(history) = web_sys::window().and_then(|w| w.history().ok()) {
+            let _ = history.push_state_with_url(&JsValue::NULL, "", Some(&guarded_url));
+        }
+    });
+    Listeners {
+        _beforeunload: beforeunload,
+        _click: click,
+        _popstate: popstate,
+    }
+}
+
+/// A plain left click on a link that would navigate in this tab.
+fn is_link_click(event: &Event) -> bool {
+    let Some(mouse) = event.dyn_ref::<MouseEvent>() else {
+        return false;
+    };
+    if mouse.button() != 0 || mouse.ctrl_key() || mouse.meta_key() || mouse.shift_key() {
+        return false;
+    }
+    let link = event
+        .target()
+        .and_then(|target| target.dyn_into::<Element>().ok())
+        .and_then(|element| element.closest("a[href]").ok().flatten());
+    match link {
+        Some(link) => {
+            let href = link.get_attribute("href").unwrap_or_default();
+            link.get_attribute("target").is_none() && !href.starts_with('#')
+        }
+        None => false,
+    }
+}
@@ -1,109 +1,315 @@
+use std::future::Future;
+use std::rc::Rc;
+
+use yew::platform::spawn_local;
 use yew::prelude::*;
-use yew::suspense::{Suspension, SuspensionResult};
 
-use crate::api::crates::{api_crate_show, api_crates, Crate};
-use crate::api::rustaceans::{api_rustacean_show, api_rustaceans, Rustacean};
+use crate::api::client::ApiClient;
+use crate::api::crates::{api_crate_show, api_crates, api_crates_page, Crate};
+use crate::api::error::ApiError;
+use crate::api::page::{Page, PageQuery};
+use crate::api::rustaceans::{api_rustacean_show, api_rustaceans, api_rustaceans_page, Rustacean};
+use crate::api::user::Role;
+use crate::cache::QueryCache;
+use crate::contexts::CurrentUserContext;
+
+/// Suffix of every page title.
+const APP_NAME: &str = "cr8s";
+
+/// What `use_api_query` hands back to the component. `data` is kept while a
+/// refetch is in flight, so a page never flashes back to "Loading ...".
+pub struct QueryHandle<T> {
+    pub data: Option<Rc<T>>,
+    pub error: Option<ApiError>,
+    pub loading: bool,
+    pub refetch: Callback<()>,
+}
 
+#[derive(Clone, Default)]
+struct QueryState {
+    error: Option<ApiError>,
+    loading: bool,
+}
+
+/// Serve `key` from the `QueryCache`, running `fetcher` when it is missing or
+/// stale, and whenever `refetch` is emitted. Results land in the cache, so every
+/// query on the same key shares them; responses to superseded requests are dropped.
 #[hook]
-pub fn use_rustaceans(token: &str) -> SuspensionResult<Vec<Rustacean>> {
-    let result_handle = use_state(|| None);
-    let result = (*result_handle).clone();
-
-    let suspension_handle = use_state(|| {
-        let cloned_token = token.to_owned();
-        Suspension::from_future(async move {
-            match api_rustaceans(&cloned_token).await {
-                Ok(rustaceans) => result_handle.set(Some(rustaceans)),
-                Err(_) => result_handle.set(Some(vec![])),
-            }
-        })
-    });
-    let suspension = (*suspension_handle).clone();
+pub fn use_api_query<T, F, Fut>(key: String, fetcher: F) -> QueryHandle<T>
+where
+    T: 'static,
+    F: Fn() -> Fut + 'static,
+    Fut: Future<Output = Result<T, ApiError>> + 'static,
+{
+    // ---
+    let cache = use_context::<QueryCache>().expect("Query cache context is missing");
+    let state = use_state(QueryState::default);
+    let generation = use_state(|| 0_u32);
+    let latest_request = use_mut_ref(|| 0_u64);
+    let last_key = use_mut_ref(|| None::<String>);
+    let last_generation = use_mut_ref(|| 0_u32);
+
+    // Always run the fetcher from the latest render so it sees current props.
+    let fetcher_ref = use_mut_ref(|| None::<Rc<F>>);
+    *fetcher_ref.borrow_mut() = Some(Rc::new(fetcher));
+
+    // Re-render whenever another query or a mutation touches this key.
+    let force_update = use_force_update();
+    {
+        let cache = cache.clone();
+        use_effect_with(key.clone(), move |key| {
+            let subscription =
+                cache.subscribe(key, Callback::from(move |_| force_update.force_update()));
+            move || drop(subscription)
+        });
+    }
+
+    let cached = cache.get::<T>(&key);
+    let stale = cached.as_ref().map(|cached| cached.stale);
+
+    {
+        let state = state.clone();
+        let cache = cache.clone();
+        use_effect_with(
+            (key.clone(), *generation, stale),
+            move |(key, generation, stale)| {
+                let same_key = last_key.borrow().as_deref() == Some(key.as_str());
+                let refetch_requested = *last_generation.borrow() != *generation;
+                *last_key.borrow_mut() = Some(key.clone());
+                *last_generation.borrow_mut() = *generation;
+
+                if *stale == Some(false) && !refetch_requested {
+                    if state.loading || (!same_key && state.error.is_some()) {
+                        state.set(QueryState::default());
+                    }
+                    return;
+                }
+
+                let request_id = {
+                    let mut latest = latest_request.borrow_mut();
+                    *latest += 1;
+                    *latest
+                };
+                state.set(QueryState {
+                    error: if same_key { state.error.clone() } else { None },
+                    loading: true,
+                });
 
-    if suspension.resumed() {
-        return match result {
-            Some(v) => Ok(v),
-            None => Err(suspension),
-        };
+                let key = key.clone();
+                let fetcher = fetcher_ref
+                    .borrow()
+                    .clone()
+                    .expect("fetcher is set on render");
+                spawn_local(async move {
+                    let result = fetcher().await;
+                    if *latest_request.borrow() != request_id {
+                        return;
+                    }
+                    match result {
+                        Ok(data) => {
+                            cache.set(&key, Rc::new(data));
+                            state.set(QueryState::default());
+                        }
+                        Err(e) => state.set(QueryState {
+                            error: Some(e),
+                            loading: false,
+                        }),
+                    }
+                });
+            },
+        );
     }
 
-    Err(suspension)
+    let refetch = {
+        let generation = generation.clone();
+        Callback::from(move |_| generation.set(generation.wrapping_add(1)))
+    };
+
+    QueryHandle {
+        data: cached.map(|cached| cached.value),
+        error: state.error.clone(),
+        loading: state.loading,
+        refetch,
+    }
 }
 
 #[hook]
-pub fn use_rustacean(token: &str, id: i32) -> SuspensionResult<Rustacean> {
-    let result_handle = use_state(|| None);
-    let result = (*result_handle).clone();
-
-    let suspension_handle = use_state(|| {
-        let cloned_token = token.to_owned();
-        Suspension::from_future(async move {
-            match api_rustacean_show(&cloned_token, id).await {
-                Ok(rustacean) => result_handle.set(Some(rustacean)),
-                Err(_) => result_handle.set(None),
-            }
-        })
-    });
-    let suspension = (*suspension_handle).clone();
+pub fn use_rustaceans(client: &ApiClient) -> QueryHandle<Vec<Rustacean>> {
+    let client = client.clone();
+    use_api_query("rustaceans".to_string(), move || {
+        let client = client.clone();
+        async move { api_rustaceans(&client).await }
+    })
+}
 
-    if suspension.resumed() {
-        return match result {
-            Some(v) => Ok(v),
-            None => Err(suspension),
-        };
-    }
+#[hook]
+pub fn use_rustaceans_page(client: &ApiClient, query: PageQuery) -> QueryHandle<Page<Rustacean>> {
+    let client = client.clone();
+    use_api_query(
+        format!("rustaceans?{}", query.to_query_string()),
+        move || {
+            let client = client.clone();
+            async move { api_rustaceans_page(&client, query).await }
+        },
+    )
+}
 
-    Err(suspension)
+#[hook]
+pub fn use_rustacean(client: &ApiClient, id: i32) -> QueryHandle<Rustacean> {
+    let client = client.clone();
+    use_api_query(format!("rustaceans/{id}"), move || {
+        let client = client.clone();
+        async move { api_rustacean_show(&client, id).await }
+    })
 }
 
 #[hook]
-pub fn use_crates(token: &str) -> SuspensionResult<Vec<Crate>> {
-    let result_handle = use_state(|| None);
-    let result = (*result_handle).clone();
-
-    let suspension_handle = use_state(|| {
-        let cloned_token = token.to_owned();
-        Suspension::from_future(async move {
-            match api_crates(&cloned_token).await {
-                Ok(crates) => result_handle.set(Some(crates)),
-                Err(_) => result_handle.set(Some(vec![])),
-            }
-        })
-    });
-    let suspension = (*suspension_handle).clone();
+pub fn use_crates(client: &ApiClient) -> QueryHandle<Vec<Crate>> {
+    let client = client.clone();
+    use_api_query("crates".to_string(), move || {
+        let client = client.clone();
+        async move { api_crates(&client).await }
+    })
+}
 
-    if suspension.resumed() {
-        return match result {
-            Some(v) => Ok(v),
-            None => Err(suspension),
-        };
+#[hook]
+pub fn use_crates_page(client: &ApiClient, query: PageQuery) -> QueryHandle<Page<Crate>> {
+    let client = client.clone();
+    use_api_query(format!("crates?{}", query.to_query_string()), move || {
+        let client = client.clone();
+        async move { api_crates_page(&client, query).await }
+    })
+}
+
+#[hook]
+pub fn use_crate(client: &ApiClient, id: i32) -> QueryHandle<Crate> {
+    let client = client.clone();
+    use_api_query(format!("crates/{id}"), move || {
+        let client = client.clone();
+        async move { api_crate_show(&client, id).await }
+    })
+}
+
+/// Whatever the query cache holds under `key`, without ever fetching it;
+/// re-renders when the entry changes.
+#[hook]
+pub fn use_cached<T>(key: String) -> Option<Rc<T>>
+where
+    T: 'static,
+{
+    let cache = use_context::<QueryCache>().expect("Query cache context is missing");
+    let force_update = use_force_update();
+    {
+        let cache = cache.clone();
+        use_effect_with(key.clone(), move |key| {
+            let subscription =
+                cache.subscribe(key, Callback::from(move |_| force_update.force_update()));
+            move || drop(subscription)
+        });
     }
+    cache.get::<T>(&key).map(|cached| cached.value)
+}
+
+/// Whether the current user may do what `role` allows; re-renders on login and
+/// logout.
+#[hook]
+pub fn use_has_role(role: Role) -> bool {
+    let current_user_ctx =
+        use_context::<CurrentUserContext>().expect("Current user context is missing");
+    current_user_ctx.has_role(role)
+}
 
-    Err(suspension)
+/// `CurrentUser::storage_scope` of the current user.
+#[hook]
+pub fn use_storage_scope() -> Option<String> {
+    let current_user_ctx =
+        use_context::<CurrentUserContext>().expect("Current user context is missing");
+    current_user_ctx.storage_scope()
 }
 
+/// Set the browser tab title to `title`, followed by the app name.
 #[hook]
-pub fn use_crate(token: &str, id: i32) -> SuspensionResult<Crate> {
-    let result_handle = use_state(|| None);
-    let result = (*result_handle).clone();
-
-    let suspension_handle = use_state(|| {
-        let cloned_token = token.to_owned();
-        Suspension::from_future(async move {
-            match api_crate_show(&cloned_token, id).await {
-                Ok(a_crate) => result_handle.set(Some(a_crate)),
-                Err(_) => result_handle.set(None),
-            }
-        })
+pub fn use_title(title: &str) {
+    let title = title.to_string();
+    use_effect_with(title, |title| {
+        if let Some(document) = web_sys::window().and_then(|window| window.document()) {
+            document.set_title(&format!("{title} · {APP_NAME}"));
+        }
     });
-    let suspension = (*suspension_handle).clone();
+}
 
-    if suspension.resumed() {
-        return match result {
-            Some(v) => Ok(v),
-            None => Err(suspension),
-        };
+/// Cache bookkeeping after a crate was created or updated: the single-crate
+/// entry is replaced and the row is patched in place wherever a cached list or
+/// list page shows it, then everything is revalidated. A new crate only appears
+/// in the paged lists once they are refetched, as its position is unknown.
+pub fn crate_saved(cache: &QueryCache, cr8: &Crate) {
+    cache.invalidate("crates");
+    cache.set(&format!("crates/{}", cr8.id), Rc::new(cr8.clone()));
+    cache.update::<Vec<Crate>>("crates", |crates| upsert(crates, cr8, |c| c.id == cr8.id));
+    cache.update_all::<Page<Crate>>("crates", |page| {
+        replace_in_page(page, cr8, |c| c.id == cr8.id)
+    });
+}
+
+pub fn crate_deleted(cache: &QueryCache, id: i32) {
+    cache.remove(&format!("crates/{id}"));
+    cache.update::<Vec<Crate>>("crates", |crates| {
+        crates.iter().filter(|c| c.id != id).cloned().collect()
+    });
+    cache.update_all::<Page<Crate>>("crates", |page| remove_from_page(page, |c| c.id == id));
+    cache.invalidate("crates");
+}
+
+pub fn rustacean_saved(cache: &QueryCache, rustacean: &Rustacean) {
+    cache.invalidate("rustaceans");
+    cache.set(
+        &format!("rustaceans/{}", rustacean.id),
+        Rc::new(rustacean.clone()),
+    );
+    cache.update::<Vec<Rustacean>>("rustaceans", |rustaceans| {
+        upsert(rustaceans, rustacean, |r| r.id == rustacean.id)
+    });
+    cache.update_all::<Page<Rustacean>>("rustaceans", |page| {
+        replace_in_page(page, rustacean, |r| r.id == rustacean.id)
+    });
+}
+
+/// The backend may cascade the delete to the rustacean's crates, so those are
+/// revalidated too.
+pub fn rustacean_deleted(cache: &QueryCache, id: i32) {
+    cache.remove(&format!("rustaceans/{id}"));
+    cache.update::<Vec<Rustacean>>("rustaceans", |rustaceans| {
+        rustaceans.iter().filter(|r| r.id != id).cloned().collect()
+    });
+    cache
+        .update_all::<Page<Rustacean>>("rustaceans", |page| remove_from_page(page, |r| r.id == id));
+    cache.invalidate("rustaceans");
+    cache.invalidate("crates");
+}
+
+fn upsert<T: Clone>(items: &[T], item: &T, is_same: impl Fn(&T) -> bool) -> Vec<T> {
+    let mut items = items.to_vec();
+    match items.iter().position(is_same) {
+        Some(index) => items[index] = item.clone(),
+        None => items.push(item.clone()),
+    }
+    items
+}
+
+fn replace_in_page<T: Clone>(page: &Page<T>, item: &T, is_same: impl Fn(&T) -> bool) -> Page<T> {
+    let mut page = page.clone();
+    if let Some(row) = page.items.iter_mut().find(|row| is_same(row)) {
+        *row = item.clone();
     }
+    page
+}
 
-    Err(suspension)
+fn remove_from_page<T: Clone>(page: &Page<T>, is_gone: impl Fn(&T) -> bool) -> Page<T> {
+    let mut page = page.clone();
+    let before = page.items.len();
+    page.items.retain(|row| !is_gone(row));
+    if page.items.len() < before {
+        page.total = page.total.map(|total| total.saturating_sub(1));
+    }
+    page
 }
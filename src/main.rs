@@ -4,11 +4,24 @@ use wasm_logger::Config;
 use yew::prelude::*;
 use yew_router::prelude::*;
 
+use components::app_layout::AppLayout;
+use components::require_auth::RequireAuth;
+
 mod api;
+mod cache;
 mod components;
+mod config;
 mod contexts;
+mod drafts;
+mod form;
+mod format;
+mod guard;
 mod hooks;
+mod markdown;
 mod pages;
+mod releases;
+mod semver;
+mod session;
 
 #[derive(Routable, PartialEq, Clone)]
 enum Route {
@@ -18,6 +31,8 @@ enum Route {
     Rustaceans,
     #[at("/rustaceans/add")]
     RustaceansAdd,
+    #[at("/rustaceans/:id")]
+    RustaceansShow { id: i32 },
     #[at("/rustaceans/:id/edit")]
     RustaceansEdit { id: i32 },
     #[at("/rustaceans/:id/delete")]
@@ -26,6 +41,8 @@ enum Route {
     Crates,
     #[at("/crates/add")]
     CratesAdd,
+    #[at("/crates/:id")]
+    CratesShow { id: i32 },
     #[at("/crates/:id/edit")]
     CratesEdit { id: i32 },
     #[at("/crates/:id/delete")]
@@ -39,34 +56,57 @@ enum Route {
 
 fn switch(route: Route) -> Html {
     match route {
-        Route::Home => html! { <pages::home::Home /> },
         Route::Login => html! { <pages::login::Login /> },
         Route::NotFound => html! { <pages::not_found::NotFound /> },
-        Route::Rustaceans => html! { <pages::rustaceans::index::Rustaceans /> },
-        Route::RustaceansAdd => html! { <pages::rustaceans::add::RustaceansAdd /> },
+        Route::Home => protected(html! { <pages::home::Home /> }),
+        Route::Rustaceans => protected(html! { <pages::rustaceans::index::Rustaceans /> }),
+        Route::RustaceansAdd => protected(html! { <pages::rustaceans::add::RustaceansAdd /> }),
+        Route::RustaceansShow { id } => {
+            protected(html! { <pages::rustaceans::show::RustaceansShow rustacean_id={id} /> })
+        }
         Route::RustaceansEdit { id } => {
-            html! { <pages::rustaceans::edit::RustaceansEdit rustacean_id={id} /> }
+            protected(html! { <pages::rustaceans::edit::RustaceansEdit rustacean_id={id} /> })
         }
         Route::RustaceansDelete { id } => {
-            html! { <pages::rustaceans::delete::RustaceansDelete rustacean_id={id} /> }
+            protected(html! { <pages::rustaceans::delete::RustaceansDelete rustacean_id={id} /> })
+        }
+        Route::Crates => protected(html! { <pages::crates::index::Crates /> }),
+        Route::CratesAdd => protected(html! { <pages::crates::add::CratesAdd /> }),
+        Route::CratesShow { id } => {
+            protected(html! { <pages::crates::show::CratesShow crate_id={id} /> })
+        }
+        Route::CratesEdit { id } => {
+            protected(html! { <pages::crates::edit::CratesEdit crate_id={id} /> })
         }
-        Route::Crates => html! { <pages::crates::index::Crates /> },
-        Route::CratesAdd => html! { <pages::crates::add::CratesAdd /> },
-        Route::CratesEdit { id } => html! { <pages::crates::edit::CratesEdit crate_id={id} /> },
         Route::CratesDelete { id } => {
-            html! { <pages::crates::delete::CratesDelete crate_id={id} /> }
+            protected(html! { <pages::crates::delete::CratesDelete crate_id={id} /> })
         }
     }
 }
 
+/// A page only reachable when logged in, inside the app layout.
+fn protected(page: Html) -> Html {
+    html! {
+        <RequireAuth>
+            <AppLayout>
+                {page}
+            </AppLayout>
+        </RequireAuth>
+    }
+}
+
 #[function_component(App)]
 fn app() -> Html {
     html! {
-        <BrowserRouter>
-            <contexts::CurrentUserProvider>
-                <Switch<Route> render={switch} />
-            </contexts::CurrentUserProvider>
-        </BrowserRouter>
+        <contexts::AppConfigProvider>
+            <BrowserRouter>
+                <contexts::CurrentUserProvider>
+                    <contexts::QueryCacheProvider>
+                        <Switch<Route> render={switch} />
+                    </contexts::QueryCacheProvider>
+                </contexts::CurrentUserProvider>
+            </BrowserRouter>
+        </contexts::AppConfigProvider>
     }
 }
 
@@ -0,0 +1,162 @@
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag};
+
+/// URL schemes links may use; anything else (`javascript:`, `data:`, ...) is
+/// replaced by `#`.
+const SAFE_LINK_SCHEMES: [&str; 3] = ["http", "https", "mailto"];
+const SAFE_IMAGE_SCHEMES: [&str; 2] = ["http", "https"];
+
+/// Render Markdown (CommonMark plus tables and strikethrough) to HTML that is
+/// safe to inject: raw HTML in the source is shown as text and link or image
+/// URLs with unexpected schemes are neutralised.
+pub fn to_html(source: &str) -> String {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH;
+    let events = Parser::new_ext(source, options).map(|event| match event {
+        Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: safe_url(dest_url, &SAFE_LINK_SCHEMES),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: safe_url(dest_url, &SAFE_IMAGE_SCHEMES),
+            title,
+            id,
+        }),
+        event => event,
+    });
+
+    let mut output = String::new();
+    html::push_html(&mut output, events);
+    output
+}
+
+/// Cut `source` to roughly `limit` characters at a word boundary, closing any
+/// code fence left open. Returns `None` if it already fits.
+pub fn truncate(source: &str, limit: usize) -> Option<String> {
+    if source.chars().count() <= limit {
+        return None;
+    }
+    let cut = source
+        .char_indices()
+        .nth(limit)
+        .map(|(index, _)| index)
+        .unwrap_or(source.len());
+    let head = &source[..cut];
+    let head = match head.rfind(char::is_whitespace) {
+        Some(space) if space > cut / 2 => &head[..space],
+        _ => head,
+    };
+    let mut excerpt = head.trim_end().to_string();
+    let open_fences = excerpt
+        .lines()
+        .filter(|line| line.trim_start().starts_with("```"))
+        .count();
+    if open_fences % 2 == 1 {
+        excerpt.push_str("\n```");
+    } else {
+        excerpt.push('…');
+    }
+    Some(excerpt)
+}
+
+fn safe_url<'a>(url: CowStr<'a>, schemes: &[&str]) -> CowStr<'a> {
+    let trimmed = url.trim();
+    // A scheme is whatever precedes the first ':' as long as no '/', '?' or
+    // '#' comes before it; without one the URL is relative.
+    let scheme = trimmed
+        .split_once(':')
+        .map(|(scheme, _)| scheme)
+        .filter(|scheme| !scheme.contains(['/', '?', '#']));
+    match scheme {
+        None => url,
+        Some(scheme) if schemes.iter().any(|s| s.eq_ignore_ascii_case(scheme)) => url,
+        Some(_) => CowStr::Borrowed("#"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_html_becomes_text() {
+        let html = to_html("<script>alert(1)</script>\n\nhi <b onclick=\"x()\">there</b>");
+        assert!(!html.contains("<script"), "{html}");
+        assert!(!html.contains("<b "), "{html}");
+        assert!(html.contains("&lt;script&gt;"), "{html}");
+        assert!(html.contains("&lt;b onclick="), "{html}");
+    }
+
+    #[test]
+    fn unsafe_links_are_neutralised() {
+        for url in [
+            "javascript:alert(1)",
+            "JaVaScRiPt:alert(1)",
+            "java&#9;script:alert(1)",
+            "data:text/html;base64,PHNjcmlwdD4=",
+        ] {
+            let html = to_html(&format!("[x]({url})"));
+            assert!(html.contains(r##"<a href="#">"##), "{url}: {html}");
+        }
+    }
+
+    #[test]
+    fn unsafe_images_are_neutralised() {
+        for url in [
+            "javascript:alert(1)",
+            "data:image/png;base64,AAAA",
+            "mailto:a@b.c",
+        ] {
+            let html = to_html(&format!("![x]({url})"));
+            assert!(html.contains(r##"<img src="#""##), "{url}: {html}");
+        }
+    }
+
+    #[test]
+    fn relative_and_mailto_urls_are_kept() {
+        assert!(to_html("[x](/crates/3)").contains(r#"href="/crates/3""#));
+        assert!(to_html("[x](docs/a:b)").contains(r#"href="docs/a:b""#));
+        assert!(to_html("[x](?q=a:b)").contains(r#"href="?q=a:b""#));
+        assert!(to_html("[x](mailto:ferris@rust-lang.org)")
+            .contains(r#"href="mailto:ferris@rust-lang.org""#));
+        assert!(to_html("![x](https://example.com/a.png)")
+            .contains(r#"src="https://example.com/a.png""#));
+    }
+
+    #[test]
+    fn truncate_leaves_short_text_alone() {
+        assert_eq!(truncate("short", 10), None);
+        assert_eq!(truncate("ünïcödé", 7), None);
+    }
+
+    #[test]
+    fn truncate_cuts_multi_byte_text_on_char_boundaries() {
+        let source = "ääää öööö üüüü ßßßß";
+        let excerpt = truncate(source, 12).unwrap();
+        assert_eq!(excerpt, "ääää öööö…");
+
+        let excerpt = truncate("日本語のテキストです", 4).unwrap();
+        assert_eq!(excerpt, "日本語の…");
+    }
+
+    #[test]
+    fn truncate_closes_an_open_code_fence() {
+        let source = "Example:\n\n```rust\nfn main() {\n    println!(\"hello\");\n}\n```";
+        let excerpt = truncate(source, 30).unwrap();
+        assert!(excerpt.ends_with("\n```"), "{excerpt}");
+        let fences = excerpt.lines().filter(|l| l.starts_with("```")).count();
+        assert_eq!(fences, 2, "{excerpt}");
+    }
+}
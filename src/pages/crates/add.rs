@@ -1,53 +1,44 @@
 use yew::prelude::*;
-use yew_router::prelude::*;
 
+use crate::api::client::ApiClient;
+use crate::api::user::Role;
 use crate::components::crate_form::CrateForm;
-use crate::components::header::Header;
-use crate::components::sidebar::Sidebar;
-use crate::contexts::CurrentUserContext;
-use crate::hooks::use_rustaceans;
-use crate::Route;
+use crate::components::query_status::QueryStatus;
+use crate::components::require_auth::use_api_client;
+use crate::components::require_role::RequireRole;
+use crate::hooks::{use_rustaceans, use_title};
 
 #[function_component(CratesAdd)]
 pub fn crates_add() -> Html {
-    let current_user_ctx =
-        use_context::<CurrentUserContext>().expect("Current user context is missing");
+    let client = use_api_client();
+    use_title("New crate");
 
-    match &current_user_ctx.token {
-        Some(token) => {
-            let loading = html! { <p>{"Loading ..."}</p> };
-            html! {
-                <div class="container">
-                    <div class="row">
-                        <div class="col-sm-auto">
-                            <Sidebar />
-                        </div>
-                        <div class="col mt-3">
-                            <Header />
-                            <Suspense fallback={loading}>
-                                <CrateAddForm token={token.clone()} />
-                            </Suspense>
-                        </div>
-                    </div>
-                </div>
-            }
-        }
-        None => html! {
-            <Redirect<Route> to={Route::Login} />
-        },
+    html! {
+        <RequireRole role={Role::Editor}>
+            <CrateAddForm client={client} />
+        </RequireRole>
     }
 }
 
 #[derive(Properties, PartialEq)]
 struct CrateAddFormProps {
-    pub token: AttrValue,
+    pub client: ApiClient,
 }
 
 #[function_component(CrateAddForm)]
-fn crate_add_form(props: &CrateAddFormProps) -> HtmlResult {
-    let rustaceans = use_rustaceans(props.token.as_str())?;
+fn crate_add_form(props: &CrateAddFormProps) -> Html {
+    let rustaceans = use_rustaceans(&props.client);
+    let Some(authors) = rustaceans.data.clone() else {
+        return html! {
+            <QueryStatus
+                error={rustaceans.error.clone()}
+                loading={rustaceans.loading}
+                onretry={rustaceans.refetch.clone()}
+            />
+        };
+    };
 
-    Ok(html! {
-        <CrateForm authors={rustaceans} />
-    })
+    html! {
+        <CrateForm authors={(*authors).clone()} />
+    }
 }
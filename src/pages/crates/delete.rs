@@ -3,10 +3,13 @@ use yew::prelude::*;
 use yew_router::prelude::*;
 
 use crate::api::crates::api_crate_delete;
+use crate::api::user::Role;
+use crate::cache::QueryCache;
 use crate::components::alert::Alert;
-use crate::components::header::Header;
-use crate::components::sidebar::Sidebar;
-use crate::contexts::CurrentUserContext;
+use crate::components::require_auth::use_api_client;
+use crate::components::require_role::RequireRole;
+use crate::hooks::{crate_deleted, use_storage_scope, use_title};
+use crate::releases;
 use crate::Route;
 
 #[derive(Properties, PartialEq)]
@@ -17,52 +20,46 @@ pub struct Props {
 #[function_component(CratesDelete)]
 pub fn crates_delete(props: &Props) -> Html {
     let navigator = use_navigator().expect("Navigator not available");
-    let current_user_ctx =
-        use_context::<CurrentUserContext>().expect("Current user context is missing");
+    let client = use_api_client();
+    use_title(&format!("Delete crate #{}", props.crate_id));
+    let cache = use_context::<QueryCache>().expect("Query cache context is missing");
+    let scope = use_storage_scope();
 
     let error_message_handle = use_state(String::default);
     let error_message = (*error_message_handle).clone();
 
-    match &current_user_ctx.token {
-        Some(token) => {
-            let crate_id = props.crate_id;
-            let cloned_token = token.to_owned();
-            let onclick = Callback::from(move |e: MouseEvent| {
-                e.prevent_default();
+    let crate_id = props.crate_id;
+    let onclick = Callback::from(move |e: MouseEvent| {
+        e.prevent_default();
 
-                let cloned_navigator = navigator.clone();
-                let cloned_error_handle = error_message_handle.clone();
-                let cloned_token = cloned_token.clone();
-                spawn_local(async move {
-                    match api_crate_delete(&cloned_token, crate_id).await {
-                        Ok(()) => cloned_navigator.push(&Route::Crates),
-                        Err(e) => cloned_error_handle.set(e.to_string()),
+        let cloned_navigator = navigator.clone();
+        let cloned_error_handle = error_message_handle.clone();
+        let cloned_client = client.clone();
+        let cloned_cache = cache.clone();
+        let cloned_scope = scope.clone();
+        spawn_local(async move {
+            match api_crate_delete(&cloned_client, crate_id).await {
+                Ok(()) => {
+                    crate_deleted(&cloned_cache, crate_id);
+                    if let Some(scope) = &cloned_scope {
+                        releases::forget(scope, crate_id);
                     }
-                });
-            });
-            html! {
-                <div class="container">
-                    <div class="row">
-                        <div class="col-sm-auto">
-                            <Sidebar />
-                        </div>
-                        <div class="col mt-3">
-                            <Header />
-                            if !error_message.is_empty() {
-                                <Alert alert_type={"danger"} message={error_message} />
-                            }
-                            <p>
-                                {"Are you sure you want to delete crate #"}
-                                {crate_id}
-                            </p>
-                            <button onclick={onclick} class="btn btn-danger">{"Delete"}</button>
-                        </div>
-                    </div>
-                </div>
+                    cloned_navigator.push(&Route::Crates)
+                }
+                Err(e) => cloned_error_handle.set(e.to_string()),
             }
-        }
-        None => html! {
-            <Redirect<Route> to={Route::Login} />
-        },
+        });
+    });
+    html! {
+        <RequireRole role={Role::Editor}>
+            if !error_message.is_empty() {
+                <Alert alert_type={"danger"} message={error_message} />
+            }
+            <p>
+                {"Are you sure you want to delete crate #"}
+                {crate_id}
+            </p>
+            <button onclick={onclick} class="btn btn-danger">{"Delete"}</button>
+        </RequireRole>
     }
 }
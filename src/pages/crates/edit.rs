@@ -1,12 +1,12 @@
 use yew::prelude::*;
-use yew_router::prelude::*;
 
+use crate::api::client::ApiClient;
+use crate::api::user::Role;
 use crate::components::crate_form::CrateForm;
-use crate::components::header::Header;
-use crate::components::sidebar::Sidebar;
-use crate::contexts::CurrentUserContext;
-use crate::hooks::{use_crate, use_rustaceans};
-use crate::Route;
+use crate::components::query_status::QueryStatus;
+use crate::components::require_auth::use_api_client;
+use crate::components::require_role::RequireRole;
+use crate::hooks::{use_crate, use_rustaceans, use_title};
 
 #[derive(Properties, PartialEq)]
 pub struct Props {
@@ -15,49 +15,49 @@ pub struct Props {
 
 #[function_component(CratesEdit)]
 pub fn crates_edit(props: &Props) -> Html {
-    let current_user_ctx =
-        use_context::<CurrentUserContext>().expect("Current user context is missing");
+    let client = use_api_client();
 
-    match &current_user_ctx.token {
-        Some(token) => {
-            let loading = html! { <p>{"Loading ..."}</p> };
-
-            html! {
-                <div class="container">
-                    <div class="row">
-                        <div class="col-sm-auto">
-                            <Sidebar />
-                        </div>
-                        <div class="col mt-3">
-                            <Header />
-                            <Suspense fallback={loading}>
-                                <CrateEditForm
-                                    crate_id={props.crate_id}
-                                    token={token.clone()}
-                                />
-                            </Suspense>
-                        </div>
-                    </div>
-                </div>
-            }
-        }
-        None => html! {
-            <Redirect<Route> to={Route::Login} />
-        },
+    html! {
+        <RequireRole role={Role::Editor}>
+            <CrateEditForm
+                crate_id={props.crate_id}
+                client={client}
+            />
+        </RequireRole>
     }
 }
 
 #[derive(Properties, PartialEq)]
 struct CrateEditFormProps {
     pub crate_id: i32,
-    pub token: AttrValue,
+    pub client: ApiClient,
 }
 
 #[function_component(CrateEditForm)]
-fn crate_edit_form(props: &CrateEditFormProps) -> HtmlResult {
-    let cr8 = use_crate(props.token.as_str(), props.crate_id)?;
-    let rustaceans = use_rustaceans(props.token.as_str())?;
-    Ok(html! {
-        <CrateForm cr8={cr8} authors={rustaceans} />
-    })
+fn crate_edit_form(props: &CrateEditFormProps) -> Html {
+    let cr8 = use_crate(&props.client, props.crate_id);
+    let rustaceans = use_rustaceans(&props.client);
+    use_title(&match &cr8.data {
+        Some(cr8) => format!("Edit {}", cr8.name),
+        None => "Edit crate".to_string(),
+    });
+    let (Some(data), Some(authors)) = (cr8.data.clone(), rustaceans.data.clone()) else {
+        let refetch_crate = cr8.refetch.clone();
+        let refetch_rustaceans = rustaceans.refetch.clone();
+        let onretry = Callback::from(move |_| {
+            refetch_crate.emit(());
+            refetch_rustaceans.emit(());
+        });
+        return html! {
+            <QueryStatus
+                error={cr8.error.clone().or(rustaceans.error.clone())}
+                loading={cr8.loading || rustaceans.loading}
+                onretry={onretry}
+            />
+        };
+    };
+
+    html! {
+        <CrateForm cr8={(*data).clone()} authors={(*authors).clone()} />
+    }
 }
@@ -1,38 +1,15 @@
 use yew::prelude::*;
-use yew_router::prelude::*;
 
 use crate::components::crate_list::CrateList;
-use crate::components::header::Header;
-use crate::components::sidebar::Sidebar;
-use crate::contexts::CurrentUserContext;
-use crate::Route;
+use crate::components::require_auth::use_api_client;
+use crate::hooks::use_title;
 
 #[function_component(Crates)]
 pub fn crates() -> Html {
-    let current_user_ctx =
-        use_context::<CurrentUserContext>().expect("Current user context is missing");
+    let client = use_api_client();
+    use_title("Crates");
 
-    match &current_user_ctx.token {
-        Some(token) => {
-            let loading = html! { <p>{"Loading ..."}</p> };
-            html! {
-                <div class="container">
-                    <div class="row">
-                        <div class="col-sm-auto">
-                            <Sidebar />
-                        </div>
-                        <div class="col mt-3">
-                            <Header />
-                            <Suspense fallback={loading}>
-                                <CrateList token={token.clone()} />
-                            </Suspense>
-                        </div>
-                    </div>
-                </div>
-            }
-        }
-        None => html! {
-            <Redirect<Route> to={Route::Login} />
-        },
+    html! {
+        <CrateList client={client} />
     }
 }
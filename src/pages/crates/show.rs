@@ -0,0 +1,147 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::api::client::ApiClient;
+use crate::api::error::ApiError;
+use crate::api::user::Role;
+use crate::components::app_layout::PageActions;
+use crate::components::markdown::Markdown;
+use crate::components::query_status::QueryStatus;
+use crate::components::require_auth::use_api_client;
+use crate::format::format_timestamp;
+use crate::hooks::{use_crate, use_has_role, use_rustacean, use_storage_scope, use_title};
+use crate::releases::releases;
+use crate::Route;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub crate_id: i32,
+}
+
+#[function_component(CratesShow)]
+pub fn crates_show(props: &Props) -> Html {
+    let client = use_api_client();
+
+    html! {
+        <CrateDetails crate_id={props.crate_id} client={client} />
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct CrateDetailsProps {
+    pub crate_id: i32,
+    pub client: ApiClient,
+}
+
+#[function_component(CrateDetails)]
+fn crate_details(props: &CrateDetailsProps) -> Html {
+    let cr8 = use_crate(&props.client, props.crate_id);
+    let can_edit = use_has_role(Role::Editor);
+    let scope = use_storage_scope();
+    use_title(cr8.data.as_ref().map_or("Crate", |c| c.name.as_str()));
+    let Some(data) = cr8.data.clone() else {
+        return html! {
+            <QueryStatus
+                error={cr8.error.clone()}
+                loading={cr8.loading}
+                onretry={cr8.refetch.clone()}
+            />
+        };
+    };
+
+    let history = scope
+        .map(|scope| releases(&scope, data.id))
+        .unwrap_or_default();
+    let description = data.description.clone().unwrap_or_default();
+
+    html! {
+        <>
+            <h2>{data.name.clone()}</h2>
+            if can_edit {
+                <PageActions>
+                    <Link<Route>
+                        to={Route::CratesEdit { id: data.id }}
+                        classes="btn btn-sm btn-outline-secondary"
+                    >
+                        {"Edit"}
+                    </Link<Route>>
+                    <Link<Route>
+                        to={Route::CratesDelete { id: data.id }}
+                        classes="btn btn-sm btn-outline-danger"
+                    >
+                        {"Delete"}
+                    </Link<Route>>
+                </PageActions>
+            }
+            <dl class="row">
+                <dt class="col-sm-2">{"Code"}</dt>
+                <dd class="col-sm-10"><code>{data.code.clone()}</code></dd>
+                <dt class="col-sm-2">{"Author"}</dt>
+                <dd class="col-sm-10">
+                    <AuthorLink client={props.client.clone()} rustacean_id={data.rustacean_id} />
+                </dd>
+                <dt class="col-sm-2">{"Version"}</dt>
+                <dd class="col-sm-10">{data.version.clone()}</dd>
+                <dt class="col-sm-2">{"Created at"}</dt>
+                <dd class="col-sm-10">{format_timestamp(&data.created_at)}</dd>
+            </dl>
+            <h4>{"Description"}</h4>
+            if description.trim().is_empty() {
+                <p class="text-muted">{"No description."}</p>
+            } else {
+                <div class="mb-3"><Markdown source={description} /></div>
+            }
+            <h4>{"Local edit history"}</h4>
+            <p class="text-muted small">
+                {"Versions replaced by edits made in this browser."}
+            </p>
+            <table class="table table-sm">
+                <thead>
+                    <tr>
+                        <th>{"Version"}</th>
+                        <th>{"Replaced at"}</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    <tr class="table-active">
+                        <td>{data.version.clone()}</td>
+                        <td class="text-muted">{"current"}</td>
+                    </tr>
+                    {
+                        history.into_iter().map(|release| html! {
+                            <tr>
+                                <td>{release.version}</td>
+                                <td>{format_timestamp(&release.replaced_at)}</td>
+                            </tr>
+                        }).collect::<Html>()
+                    }
+                </tbody>
+            </table>
+        </>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct AuthorLinkProps {
+    pub client: ApiClient,
+    pub rustacean_id: i32,
+}
+
+#[function_component(AuthorLink)]
+fn author_link(props: &AuthorLinkProps) -> Html {
+    let author = use_rustacean(&props.client, props.rustacean_id);
+    match (author.data.clone(), author.error.clone()) {
+        (Some(rustacean), _) => html! {
+            <Link<Route> to={Route::RustaceansShow { id: rustacean.id }}>
+                {rustacean.name.clone()}
+            </Link<Route>>
+        },
+        (None, Some(ApiError::NotFound)) => html! {
+            <span class="text-muted">{format!("Unknown author (#{})", props.rustacean_id)}</span>
+        },
+        (None, Some(e)) => html! {
+            <span class="text-danger">{e.to_string()}</span>
+        },
+        (None, None) => html! { {"Loading ..."} },
+    }
+}
@@ -1,21 +1,12 @@
 use yew::prelude::*;
 
-use crate::components::header::Header;
-use crate::components::sidebar::Sidebar;
+use crate::hooks::use_title;
 
 #[function_component(Home)]
 pub fn home() -> Html {
+    use_title("Home");
+
     html! {
-        <div class="container">
-            <div class="row">
-                <div class="col-sm-auto">
-                    <Sidebar />
-                </div>
-                <div class="col mt-3">
-                    <Header />
-                    {"Have a great day!"}
-                </div>
-            </div>
-        </div>
+        {"Have a great day!"}
     }
 }
@@ -1,34 +1,53 @@
 use yew::prelude::*;
 use yew_router::prelude::*;
 
+use crate::components::alert::Alert;
 use crate::components::login_form::LoginForm;
+use crate::components::login_redirect::{replace_next, LoginQuery};
+use crate::components::splash::Splash;
 use crate::contexts::CurrentUserContext;
-use crate::Route;
+use crate::hooks::use_title;
 
 #[function_component(Login)]
 pub fn login() -> Html {
+    let navigator = use_navigator().expect("Navigator not available");
+    let next = use_location()
+        .and_then(|location| location.query::<LoginQuery>().ok())
+        .and_then(|query| query.next);
     let current_user_ctx =
         use_context::<CurrentUserContext>().expect("Current user context is missing");
 
-    match &current_user_ctx.user {
-        Some(_) => {
-            html! {
-                <Redirect<Route> to={Route::Home} />
-            }
+    use_title("Log in");
+
+    let logged_in = current_user_ctx.user.is_some();
+    use_effect_with(logged_in, move |logged_in| {
+        if *logged_in {
+            replace_next(&navigator, next.as_deref());
         }
-        None => {
-            html! {
-                <div class="container">
-                    <div class="row min-vh-100 justify-content-center align-items-center">
-                        <div class="col-md-4">
-                            <p class="text-center">
-                                <img src="/yew-logo.svg" alt="logo" />
-                            </p>
-                            <LoginForm />
-                        </div>
-                    </div>
+    });
+
+    if logged_in {
+        return html! {};
+    }
+    // A remembered session may log the user in without the form.
+    if current_user_ctx.is_restoring() {
+        return html! { <Splash /> };
+    }
+    let logout_warning = current_user_ctx.logout_warning.clone();
+
+    html! {
+        <div class="container">
+            <div class="row min-vh-100 justify-content-center align-items-center">
+                <div class="col-md-4">
+                    <p class="text-center">
+                        <img src="/yew-logo.svg" alt="logo" />
+                    </p>
+                    if let Some(warning) = logout_warning {
+                        <Alert alert_type="warning" message={warning} />
+                    }
+                    <LoginForm />
                 </div>
-            }
-        }
+            </div>
+        </div>
     }
 }
@@ -1,7 +1,11 @@
 use yew::prelude::*;
 
+use crate::hooks::use_title;
+
 #[function_component(NotFound)]
 pub fn not_found() -> Html {
+    use_title("Page not found");
+
     html! {
         <h1>{"Page not found"}</h1>
     }
@@ -1,22 +1,17 @@
 use yew::prelude::*;
 
-use crate::components::header::Header;
+use crate::api::user::Role;
+use crate::components::require_role::RequireRole;
 use crate::components::rustacean_form::RustaceanForm;
-use crate::components::sidebar::Sidebar;
+use crate::hooks::use_title;
 
 #[function_component(RustaceansAdd)]
 pub fn rustaceans_add() -> Html {
+    use_title("New rustacean");
+
     html! {
-        <div class="container">
-            <div class="row">
-                <div class="col-sm-auto">
-                    <Sidebar />
-                </div>
-                <div class="col mt-3">
-                    <Header />
-                    <RustaceanForm />
-                </div>
-            </div>
-        </div>
+        <RequireRole role={Role::Editor}>
+            <RustaceanForm />
+        </RequireRole>
     }
 }
@@ -3,10 +3,12 @@ use yew::prelude::*;
 use yew_router::prelude::*;
 
 use crate::api::rustaceans::api_rustacean_delete;
+use crate::api::user::Role;
+use crate::cache::QueryCache;
 use crate::components::alert::Alert;
-use crate::components::header::Header;
-use crate::components::sidebar::Sidebar;
-use crate::contexts::CurrentUserContext;
+use crate::components::require_auth::use_api_client;
+use crate::components::require_role::RequireRole;
+use crate::hooks::{rustacean_deleted, use_title};
 use crate::Route;
 
 #[derive(Properties, PartialEq)]
@@ -17,53 +19,42 @@ pub struct Props {
 #[function_component(RustaceansDelete)]
 pub fn rustaceans_delete(props: &Props) -> Html {
     let navigator = use_navigator().expect("Navigator not available");
-    let current_user_ctx =
-        use_context::<CurrentUserContext>().expect("Current user context is missing");
+    let client = use_api_client();
+    use_title(&format!("Delete rustacean #{}", props.rustacean_id));
+    let cache = use_context::<QueryCache>().expect("Query cache context is missing");
 
     let error_message_handle = use_state(String::default);
     let error_message = (*error_message_handle).clone();
 
-    match &current_user_ctx.token {
-        Some(token) => {
-            let rustacean_id = props.rustacean_id;
-            let token = token.to_owned();
-            let onclick = Callback::from(move |e: MouseEvent| {
-                e.prevent_default();
+    let rustacean_id = props.rustacean_id;
+    let onclick = Callback::from(move |e: MouseEvent| {
+        e.prevent_default();
 
-                // Clone these things so the can me 'move'd into the spawn local closeure below
-                let token = token.clone();
-                let navigator = navigator.clone();
-                let error_message_handle = error_message_handle.clone();
-                spawn_local(async move {
-                    match api_rustacean_delete(&token, rustacean_id).await {
-                        Ok(()) => navigator.push(&Route::Rustaceans),
-                        Err(e) => error_message_handle.set(e.to_string()),
-                    }
-                });
-            });
-            html! {
-                <div class="container">
-                    <div class="row">
-                        <div class="col-sm-auto">
-                            <Sidebar />
-                        </div>
-                        <div class="col mt-3">
-                            <Header />
-                            if !error_message.is_empty() {
-                                <Alert alert_type={"danger"} message={error_message} />
-                            }
-                            <p>
-                                {"Are you sure you want to delete rustacean #"}
-                                {rustacean_id}
-                            </p>
-                            <button onclick={onclick} class="btn btn-danger">{"Delete"}</button>
-                        </div>
-                    </div>
-                </div>
+        // Clone these things so the can me 'move'd into the spawn local closeure below
+        let client = client.clone();
+        let cache = cache.clone();
+        let navigator = navigator.clone();
+        let error_message_handle = error_message_handle.clone();
+        spawn_local(async move {
+            match api_rustacean_delete(&client, rustacean_id).await {
+                Ok(()) => {
+                    rustacean_deleted(&cache, rustacean_id);
+                    navigator.push(&Route::Rustaceans)
+                }
+                Err(e) => error_message_handle.set(e.to_string()),
             }
-        }
-        None => html! {
-            <Redirect<Route> to={Route::Login} />
-        },
+        });
+    });
+    html! {
+        <RequireRole role={Role::Editor}>
+            if !error_message.is_empty() {
+                <Alert alert_type={"danger"} message={error_message} />
+            }
+            <p>
+                {"Are you sure you want to delete rustacean #"}
+                {rustacean_id}
+            </p>
+            <button onclick={onclick} class="btn btn-danger">{"Delete"}</button>
+        </RequireRole>
     }
 }
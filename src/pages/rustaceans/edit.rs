@@ -1,12 +1,12 @@
 use yew::prelude::*;
-use yew_router::prelude::*;
 
-use crate::components::header::Header;
+use crate::api::client::ApiClient;
+use crate::api::user::Role;
+use crate::components::query_status::QueryStatus;
+use crate::components::require_auth::use_api_client;
+use crate::components::require_role::RequireRole;
 use crate::components::rustacean_form::RustaceanForm;
-use crate::components::sidebar::Sidebar;
-use crate::contexts::CurrentUserContext;
-use crate::hooks::use_rustacean;
-use crate::Route;
+use crate::hooks::{use_rustacean, use_title};
 
 #[derive(Properties, PartialEq)]
 pub struct Props {
@@ -15,49 +15,42 @@ pub struct Props {
 
 #[function_component(RustaceansEdit)]
 pub fn rustaceans_edit(props: &Props) -> Html {
-    let current_user_ctx =
-        use_context::<CurrentUserContext>().expect("Current user context is missing");
-
-    match &current_user_ctx.token {
-        Some(token) => {
-            let loading = html! { <p>{"Loading ..."}</p> };
-
-            html! {
-                <div class="container">
-                    <div class="row">
-                        <div class="col-sm-auto">
-                            <Sidebar />
-                        </div>
-                        <div class="col mt-3">
-                            <Header />
-                            <Suspense fallback={loading}>
-                                <RustaceanEditForm
-                                    rustacean_id={props.rustacean_id}
-                                    token={token.clone()}
-                                />
-                            </Suspense>
-                        </div>
-                    </div>
-                </div>
-            }
-        }
-        None => html! {
-            <Redirect<Route> to={Route::Login} />
-        },
+    let client = use_api_client();
+
+    html! {
+        <RequireRole role={Role::Editor}>
+            <RustaceanEditForm
+                rustacean_id={props.rustacean_id}
+                client={client}
+            />
+        </RequireRole>
     }
 }
 
 #[derive(Properties, PartialEq)]
 struct RustaceanEditFormProps {
     pub rustacean_id: i32,
-    pub token: AttrValue,
+    pub client: ApiClient,
 }
 
 #[function_component(RustaceanEditForm)]
-fn rustaceans_edit_form(props: &RustaceanEditFormProps) -> HtmlResult {
-    let rustacean = use_rustacean(props.token.as_str(), props.rustacean_id)?;
-
-    Ok(html! {
-        <RustaceanForm rustacean={rustacean} />
-    })
+fn rustaceans_edit_form(props: &RustaceanEditFormProps) -> Html {
+    let rustacean = use_rustacean(&props.client, props.rustacean_id);
+    use_title(&match &rustacean.data {
+        Some(rustacean) => format!("Edit {}", rustacean.name),
+        None => "Edit rustacean".to_string(),
+    });
+    let Some(data) = rustacean.data.clone() else {
+        return html! {
+            <QueryStatus
+                error={rustacean.error.clone()}
+                loading={rustacean.loading}
+                onretry={rustacean.refetch.clone()}
+            />
+        };
+    };
+
+    html! {
+        <RustaceanForm rustacean={(*data).clone()} />
+    }
 }
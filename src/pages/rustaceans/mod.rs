@@ -2,3 +2,4 @@ pub mod add;
 pub mod delete;
 pub mod edit;
 pub mod index;
+pub mod show;
@@ -0,0 +1,164 @@
+use std::rc::Rc;
+
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::api::client::ApiClient;
+use crate::api::crates::Crate;
+use crate::api::user::Role;
+use crate::components::app_layout::PageActions;
+use crate::components::data_table::{Column, DataTable};
+use crate::components::markdown::{Markdown, EXCERPT_LENGTH};
+use crate::components::query_status::QueryStatus;
+use crate::components::require_auth::use_api_client;
+use crate::format::format_timestamp;
+use crate::hooks::{use_crates, use_has_role, use_rustacean, use_title};
+use crate::Route;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub rustacean_id: i32,
+}
+
+#[function_component(RustaceansShow)]
+pub fn rustaceans_show(props: &Props) -> Html {
+    let client = use_api_client();
+
+    html! {
+        <RustaceanDetails rustacean_id={props.rustacean_id} client={client} />
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct RustaceanDetailsProps {
+    pub rustacean_id: i32,
+    pub client: ApiClient,
+}
+
+#[function_component(RustaceanDetails)]
+fn rustacean_details(props: &RustaceanDetailsProps) -> Html {
+    let rustacean = use_rustacean(&props.client, props.rustacean_id);
+    let crates = use_crates(&props.client);
+    let can_edit = use_has_role(Role::Editor);
+    use_title(
+        rustacean
+            .data
+            .as_ref()
+            .map_or("Rustacean", |r| r.name.as_str()),
+    );
+    let Some(data) = rustacean.data.clone() else {
+        return html! {
+            <QueryStatus
+                error={rustacean.error.clone()}
+                loading={rustacean.loading}
+                onretry={rustacean.refetch.clone()}
+            />
+        };
+    };
+
+    let crates_table = match crates.data.clone() {
+        Some(all) => {
+            let authored: Vec<Crate> = all
+                .iter()
+                .filter(|c| c.rustacean_id == data.id)
+                .cloned()
+                .collect();
+            if authored.is_empty() {
+                html! { <p class="text-muted">{"No crates yet."}</p> }
+            } else {
+                html! {
+                    <DataTable<Crate>
+                        id="rustacean-crates"
+                        columns={crate_columns(can_edit)}
+                        rows={Rc::new(authored)}
+                    />
+                }
+            }
+        }
+        None => html! {
+            <QueryStatus
+                error={crates.error.clone()}
+                loading={crates.loading}
+                onretry={crates.refetch.clone()}
+            />
+        },
+    };
+
+    html! {
+        <>
+            <h2>{data.name.clone()}</h2>
+            if can_edit {
+                <PageActions>
+                    <Link<Route>
+                        to={Route::RustaceansEdit { id: data.id }}
+                        classes="btn btn-sm btn-outline-secondary"
+                    >
+                        {"Edit"}
+                    </Link<Route>>
+                    <Link<Route>
+                        to={Route::RustaceansDelete { id: data.id }}
+                        classes="btn btn-sm btn-outline-danger"
+                    >
+                        {"Delete"}
+                    </Link<Route>>
+                </PageActions>
+            }
+            <dl class="row">
+                <dt class="col-sm-2">{"Email"}</dt>
+                <dd class="col-sm-10">
+                    <a href={format!("mailto:{}", data.email)}>{data.email.clone()}</a>
+                </dd>
+                <dt class="col-sm-2">{"Created at"}</dt>
+                <dd class="col-sm-10">{format_timestamp(&data.created_at)}</dd>
+            </dl>
+            <h4>{"Crates"}</h4>
+            {crates_table}
+        </>
+    }
+}
+
+/// Edit and delete links are only offered to editors.
+fn crate_columns(can_edit: bool) -> Vec<Column<Crate>> {
+    let mut columns = vec![
+        Column::new("code", "Code", |c: &Crate| c.code.clone()),
+        Column::new("name", "Name", |c: &Crate| c.name.clone()).render(|c: &Crate| {
+            html! {
+                <Link<Route> to={Route::CratesShow { id: c.id }}>{c.name.clone()}</Link<Route>>
+            }
+        }),
+        Column::new("version", "Version", |c: &Crate| c.version.clone()),
+        Column::new("description", "Description", |c: &Crate| {
+            c.description.clone().unwrap_or_default()
+        })
+        .render(|c: &Crate| {
+            html! {
+                <Markdown
+                    source={c.description.clone().unwrap_or_default()}
+                    limit={EXCERPT_LENGTH}
+                />
+            }
+        }),
+    ];
+    if can_edit {
+        columns.push(Column::actions("operations", "Operations", |c: &Crate| {
+            html! {
+                <>
+                    <Link<Route>
+                        to={Route::CratesEdit { id: c.id }}
+                        classes="link-secondary"
+                    >
+                        {"edit"}
+                    </Link<Route>>
+                    <span class="mx-1">{"/"}</span>
+                    <Link<Route>
+                        to={Route::CratesDelete { id: c.id }}
+                        classes="link-danger"
+                    >
+                        {"delete"}
+                    </Link<Route>>
+                </>
+            }
+        }));
+    }
+    columns
+}
@@ -0,0 +1,59 @@
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+
+use crate::api::crates::Crate;
+use crate::session;
+
+/// Entries kept per crate; the oldest are dropped first.
+const MAX_RELEASES: usize = 50;
+
+/// A version a crate used to have. The backend keeps only the current version,
+/// so the history is recorded in local storage as the crate is edited in this
+/// browser, per backend and user, and dropped on logout.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct Release {
+    pub version: String,
+    /// ISO 8601 time at which the next version replaced this one.
+    pub replaced_at: String,
+}
+
+const KEY_PREFIX: &str = "cr8s_releases_";
+
+/// `scope` is `CurrentUser::storage_scope`.
+fn storage_key(scope: &str, crate_id: i32) -> String {
+    format!("{KEY_PREFIX}{scope}_{crate_id}")
+}
+
+/// Previous versions of a crate, most recent first.
+pub fn releases(scope: &str, crate_id: i32) -> Vec<Release> {
+    LocalStorage::get(storage_key(scope, crate_id)).unwrap_or_default()
+}
+
+/// Call after `api_crate_update` succeeded; remembers `before.version` if the
+/// update changed it.
+pub fn record_update(scope: &str, before: &Crate, after: &Crate) {
+    if before.version == after.version {
+        return;
+    }
+    let mut history = releases(scope, after.id);
+    history.insert(
+        0,
+        Release {
+            version: before.version.clone(),
+            replaced_at: js_sys::Date::new_0().to_iso_string().into(),
+        },
+    );
+    history.truncate(MAX_RELEASES);
+    if let Err(e) = LocalStorage::set(storage_key(scope, after.id), &history) {
+        log::warn!("Could not record release history: {e}");
+    }
+}
+
+pub fn forget(scope: &str, crate_id: i32) {
+    LocalStorage::delete(storage_key(scope, crate_id));
+}
+
+/// Drop the history of every user, on logout.
+pub fn clear_all() {
+    session::clear_local(KEY_PREFIX);
+}
@@ -0,0 +1,349 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+/// Identifier used when a pre-release is started from a plain release.
+const DEFAULT_PRE_RELEASE: &str = "rc";
+
+/// A [SemVer 2.0](https://semver.org/spec/v2.0.0.html) version. Equality and
+/// ordering follow SemVer precedence, so `1.0.0+a == 1.0.0+b`.
+#[derive(Clone, Debug)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub pre: Vec<Identifier>,
+    /// Build metadata; kept for display but ignored by comparisons.
+    pub build: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Identifier {
+    Numeric(u64),
+    Alphanumeric(String),
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Bump {
+    Major,
+    Minor,
+    Patch,
+    PreRelease,
+}
+
+impl Bump {
+    pub const ALL: [Bump; 4] = [Bump::Major, Bump::Minor, Bump::Patch, Bump::PreRelease];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Bump::Major => "Bump major",
+            Bump::Minor => "Bump minor",
+            Bump::Patch => "Bump patch",
+            Bump::PreRelease => "Bump pre-release",
+        }
+    }
+}
+
+impl Version {
+    /// Parse `MAJOR.MINOR.PATCH[-PRE][+BUILD]`. The error is meant to be shown
+    /// next to the form field as is.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err("Version is required".to_string());
+        }
+        let (rest, build) = match input.split_once('+') {
+            Some((rest, build)) => {
+                if build.split('.').any(|part| !is_identifier(part)) {
+                    return Err(format!("Invalid build metadata \"{build}\""));
+                }
+                (rest, Some(build.to_string()))
+            }
+            None => (input, None),
+        };
+        let (core, pre) = match rest.split_once('-') {
+            Some((core, pre)) => (core, parse_pre_release(pre)?),
+            None => (rest, Vec::new()),
+        };
+
+        let numbers: Vec<&str> = core.split('.').collect();
+        let [major, minor, patch] = numbers[..] else {
+            return Err("Expected MAJOR.MINOR.PATCH, e.g. 1.4.0".to_string());
+        };
+        Ok(Self {
+            major: parse_number(major, "major")?,
+            minor: parse_number(minor, "minor")?,
+            patch: parse_number(patch, "patch")?,
+            pre,
+            build,
+        })
+    }
+
+    /// `1.4.2` becomes `2.0.0`, `1.5.0` or `1.4.3`. Bumping the patch of a
+    /// pre-release releases it (`1.5.0-rc.2` becomes `1.5.0`). A pre-release bump
+    /// increments the trailing number (`rc.1` to `rc.2`) or starts `rc.1` on the
+    /// next patch. `None` when a number would overflow.
+    pub fn bump(&self, bump: Bump) -> Option<Self> {
+        let mut next = Self {
+            build: None,
+            ..self.clone()
+        };
+        match bump {
+            Bump::Major => {
+                next.major = next.major.checked_add(1)?;
+                next.minor = 0;
+                next.patch = 0;
+                next.pre.clear();
+            }
+            Bump::Minor => {
+                next.minor = next.minor.checked_add(1)?;
+                next.patch = 0;
+                next.pre.clear();
+            }
+            Bump::Patch if self.pre.is_empty() => next.patch = next.patch.checked_add(1)?,
+            Bump::Patch => next.pre.clear(),
+            Bump::PreRelease => match next.pre.last_mut() {
+                Some(Identifier::Numeric(n)) => *n = n.checked_add(1)?,
+                Some(Identifier::Alphanumeric(_)) => next.pre.push(Identifier::Numeric(1)),
+                None => {
+                    next.patch = next.patch.checked_add(1)?;
+                    next.pre = vec![
+                        Identifier::Alphanumeric(DEFAULT_PRE_RELEASE.to_string()),
+                        Identifier::Numeric(1),
+                    ];
+                }
+            },
+        }
+        Some(next)
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            let pre: Vec<String> = self.pre.iter().map(ToString::to_string).collect();
+            write!(f, "-{}", pre.join("."))?;
+        }
+        if let Some(build) = &self.build {
+            write!(f, "+{build}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Identifier::Numeric(n) => write!(f, "{n}"),
+            Identifier::Alphanumeric(s) => f.write_str(s),
+        }
+    }
+}
+
+/// SemVer precedence: a pre-release sorts before its release, build metadata is
+/// ignored.
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => self.pre.cmp(&other.pre),
+            })
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Numeric identifiers sort before alphanumeric ones.
+impl Ord for Identifier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Identifier::Numeric(a), Identifier::Numeric(b)) => a.cmp(b),
+            (Identifier::Numeric(_), Identifier::Alphanumeric(_)) => Ordering::Less,
+            (Identifier::Alphanumeric(_), Identifier::Numeric(_)) => Ordering::Greater,
+            (Identifier::Alphanumeric(a), Identifier::Alphanumeric(b)) => a.cmp(b),
+        }
+    }
+}
+
+impl PartialOrd for Identifier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn parse_number(part: &str, name: &str) -> Result<u64, String> {
+    if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("The {name} version must be a number"));
+    }
+    if part.len() > 1 && part.starts_with('0') {
+        return Err(format!("The {name} version must not have leading zeros"));
+    }
+    part.parse()
+        .map_err(|_| format!("The {name} version is too large"))
+}
+
+fn parse_pre_release(pre: &str) -> Result<Vec<Identifier>, String> {
+    pre.split('.')
+        .map(|part| {
+            if !is_identifier(part) {
+                return Err(format!("Invalid pre-release \"{pre}\""));
+            }
+            if part.bytes().all(|b| b.is_ascii_digit()) {
+                if part.len() > 1 && part.starts_with('0') {
+                    return Err(format!("Invalid pre-release \"{pre}\": leading zero"));
+                }
+                return part
+                    .parse()
+                    .map(Identifier::Numeric)
+                    .map_err(|_| format!("Invalid pre-release \"{pre}\": number too large"));
+            }
+            Ok(Identifier::Alphanumeric(part.to_string()))
+        })
+        .collect()
+}
+
+fn is_identifier(part: &str) -> bool {
+    !part.is_empty() && part.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(input: &str) -> Version {
+        Version::parse(input).unwrap()
+    }
+
+    #[test]
+    fn parses_pre_release_and_build() {
+        let version = v("1.4.0-rc.2+build.7");
+        assert_eq!((version.major, version.minor, version.patch), (1, 4, 0));
+        assert_eq!(
+            version.pre,
+            vec![
+                Identifier::Alphanumeric("rc".into()),
+                Identifier::Numeric(2)
+            ]
+        );
+        assert_eq!(version.build.as_deref(), Some("build.7"));
+        assert_eq!(version.to_string(), "1.4.0-rc.2+build.7");
+    }
+
+    #[test]
+    fn rejects_malformed_versions() {
+        for input in [
+            "", "1", "1.2", "1.2.3.4", "01.2.3", "1.2.x", "1.2.3-", "1.2.3-01", "1.2.3+",
+        ] {
+            assert!(Version::parse(input).is_err(), "{input:?} should not parse");
+        }
+    }
+
+    #[test]
+    fn orders_pre_releases_before_their_release() {
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1",
+            "1.1.0",
+            "2.0.0",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(v(pair[0]) < v(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn build_metadata_is_ignored_by_eq_and_ord() {
+        let (a, b) = (v("1.0.0+a"), v("1.0.0+b"));
+        assert_eq!(a.cmp(&b), Ordering::Equal);
+        assert_eq!(a, b);
+        assert_ne!(v("1.0.0+a"), v("1.0.1+a"));
+    }
+
+    #[test]
+    fn bumps() {
+        assert_eq!(v("1.4.2").bump(Bump::Major).unwrap().to_string(), "2.0.0");
+        assert_eq!(
+            v("1.4.2-rc.1").bump(Bump::Major).unwrap().to_string(),
+            "2.0.0"
+        );
+        assert_eq!(v("1.4.2").bump(Bump::Minor).unwrap().to_string(), "1.5.0");
+        assert_eq!(v("1.4.2").bump(Bump::Patch).unwrap().to_string(), "1.4.3");
+        assert_eq!(
+            v("1.5.0-rc.2").bump(Bump::Patch).unwrap().to_string(),
+            "1.5.0"
+        );
+        assert_eq!(
+            v("1.4.2").bump(Bump::PreRelease).unwrap().to_string(),
+            "1.4.3-rc.1"
+        );
+        assert_eq!(
+            v("1.5.0-rc.1").bump(Bump::PreRelease).unwrap().to_string(),
+            "1.5.0-rc.2"
+        );
+        assert_eq!(
+            v("1.5.0-beta").bump(Bump::PreRelease).unwrap().to_string(),
+            "1.5.0-beta.1"
+        );
+        assert_eq!(
+            v("1.4.2+build.5").bump(Bump::Patch).unwrap().to_string(),
+            "1.4.3"
+        );
+    }
+
+    #[test]
+    fn bumps_move_forward() {
+        for input in ["0.1.0", "1.4.2", "1.5.0-rc.2", "1.5.0-beta"] {
+            for bump in Bump::ALL {
+                assert!(v(input).bump(bump).unwrap() > v(input), "{input} {bump:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn bumps_that_would_overflow_are_none() {
+        let max = u64::MAX;
+        assert_eq!(v(&format!("{max}.0.0")).bump(Bump::Major), None);
+        assert_eq!(v(&format!("1.{max}.0")).bump(Bump::Minor), None);
+        assert_eq!(v(&format!("1.0.{max}")).bump(Bump::Patch), None);
+        assert_eq!(v(&format!("1.0.{max}")).bump(Bump::PreRelease), None);
+        assert_eq!(v(&format!("1.0.0-rc.{max}")).bump(Bump::PreRelease), None);
+        // Only the overflowing number matters.
+        assert_eq!(
+            v(&format!("1.0.{max}"))
+                .bump(Bump::Minor)
+                .unwrap()
+                .to_string(),
+            "1.1.0"
+        );
+        assert_eq!(
+            v(&format!("1.0.{max}-rc.1"))
+                .bump(Bump::Patch)
+                .unwrap()
+                .to_string(),
+            format!("1.0.{max}")
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use gloo_events::EventListener;
+use gloo_storage::{LocalStorage, SessionStorage, Storage};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsCast;
+use web_sys::StorageEvent;
+use yew::prelude::*;
+
+const SESSION_KEY: &str = "cr8s_session";
+
+/// Written to local storage on every logout so other tabs follow, even those
+/// whose session lives in their own session storage.
+const LOGOUT_KEY: &str = "cr8s_logout";
+
+/// cr8s keeps sessions in Redis for three hours; assumed when the token itself
+/// carries no expiry.
+const DEFAULT_LIFETIME_MS: f64 = 3.0 * 60.0 * 60.0 * 1000.0;
+
+/// The stored login. "Remember me" keeps it in local storage, shared by every
+/// tab and surviving a browser restart; otherwise it lives in this tab's
+/// session storage.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Session {
+    pub token: String,
+    /// Milliseconds since the epoch.
+    pub expires_at: f64,
+    pub remember: bool,
+}
+
+impl Session {
+    /// A session starting now. The expiry is read from the token when it is a
+    /// JWT, otherwise the backend's default lifetime is assumed.
+    pub fn start(token: String, remember: bool) -> Self {
+        let expires_at = jwt_expiry(&token).unwrap_or_else(|| now() + DEFAULT_LIFETIME_MS);
+        Self {
+            token,
+            expires_at,
+            remember,
+        }
+    }
+
+    /// Milliseconds left, negative once expired.
+    pub fn remaining_ms(&self) -> f64 {
+        self.expires_at - now()
+    }
+}
+
+/// The stored session, unless it has expired. This tab's own session wins over
+/// a remembered one.
+pub fn load() -> Option<Session> {
+    let session = SessionStorage::get::<Session>(SESSION_KEY)
+        .or_else(|_| LocalStorage::get::<Session>(SESSION_KEY))
+        .ok()?;
+    if session.remaining_ms() <= 0.0 {
+        clear(&session.token);
+        return None;
+    }
+    Some(session)
+}
+
+pub fn save(session: &Session) {
+    let result = if session.remember {
+        SessionStorage::delete(SESSION_KEY);
+        LocalStorage::set(SESSION_KEY, session)
+    } else {
+        // A remembered session may belong to other tabs; leave it to them.
+        SessionStorage::set(SESSION_KEY, session)
+    };
+    if let Err(e) = result {
+        log::warn!("Could not store the session: {e}");
+    }
+}
+
+/// Forget the session holding `token`. A different session in the other
+/// storage, e.g. a remembered one other tabs still use, is left alone.
+pub fn clear(token: &str) {
+    if SessionStorage::get::<Session>(SESSION_KEY).is_ok_and(|s| s.token == token) {
+        SessionStorage::delete(SESSION_KEY);
+    }
+    if LocalStorage::get::<Session>(SESSION_KEY).is_ok_and(|s| s.token == token) {
+        LocalStorage::delete(SESSION_KEY);
+    }
+}
+
+/// Forget every stored session and tell other tabs to do the same.
+pub fn clear_everywhere() {
+    LocalStorage::delete(SESSION_KEY);
+    SessionStorage::delete(SESSION_KEY);
+    let _ = LocalStorage::set(LOGOUT_KEY, now());
+}
+
+/// Run `onlogout` when another tab logs out, or removes the remembered session
+/// this tab uses (`token`). Storage events only reach the tabs that did not make
+/// the change, so this tab's own logout is not echoed.
+#[hook]
+pub fn use_logout_elsewhere(token: Option<String>, onlogout: Callback<()>) {
+    use_effect_with(token, move |token| {
+        let token = token.clone();
+        let window = web_sys::window().expect("No window");
+        let listener = EventListener::new(&window, "storage", move |event| {
+            let Some(event) = event.dyn_ref::<StorageEvent>() else {
+                return;
+            };
+            match event.key().as_deref() {
+                Some(LOGOUT_KEY) => onlogout.emit(()),
+                // The remembered session was removed, e.g. because it expired or
+                // site data was cleared. A tab with a session of its own keeps it.
+                Some(SESSION_KEY) if event.new_value().is_none() => {
+                    let removed = event
+                        .old_value()
+                        .and_then(|old| serde_json::from_str::<Session>(&old).ok());
+                    if removed.is_some_and(|removed| Some(removed.token) == token) {
+                        onlogout.emit(());
+                    }
+                }
+                // `localStorage.clear()` reports no key.
+                None => onlogout.emit(()),
+                _ => {}
+            }
+        });
+        move || drop(listener)
+    });
+}
+
+/// Remove every local storage entry whose key starts with `prefix`, e.g. data
+/// the logged out user left behind.
+pub fn clear_local(prefix: &str) {
+    let storage = LocalStorage::raw();
+    let keys: Vec<String> = (0..storage.length().unwrap_or(0))
+        .filter_map(|index| storage.key(index).ok().flatten())
+        .filter(|key| key.starts_with(prefix))
+        .collect();
+    for key in keys {
+        let _ = storage.remove_item(&key);
+    }
+}
+
+fn now() -> f64 {
+    js_sys::Date::now()
+}
+
+/// The `exp` claim of a JWT, in milliseconds. `None` for opaque tokens.
+fn jwt_expiry(token: &str) -> Option<f64> {
+    #[derive(Deserialize)]
+    struct Claims {
+        exp: f64,
+    }
+
+    let mut parts = token.split('.');
+    let (Some(_), Some(payload), Some(_), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return None;
+    };
+    // JWTs use unpadded base64url; `atob` wants padded standard base64.
+    let mut base64: String = payload
+        .chars()
+        .map(|c| match c {
+            '-' => '+',
+            '_' => '/',
+            c => c,
+        })
+        .collect();
+    while !base64.len().is_multiple_of(4) {
+        base64.push('=');
+    }
+    let json = web_sys::window()?.atob(&base64).ok()?;
+    let claims: Claims = serde_json::from_str(&json).ok()?;
+    Some(claims.exp * 1000.0)
+}
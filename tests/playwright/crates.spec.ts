@@ -18,29 +18,68 @@ test('can add a crate', async ({ page }) => {
   await page.fill('input[name="name"]', name);
   await page.fill('input[name="version"]', version);
 
-  // Select "Playwright Test" from the dropdown and capture its ID (value)
-  const authorId = await page
-    .locator('select[name="author"] option', { hasText: 'Playwright Test' })
+  // Pick "Playwright Test" in the author combobox and capture its ID
+  await page.fill('input[name="author-search"]', 'Playwright Test');
+  await page
+    .locator('li[role="option"]', { hasText: 'Playwright Test' })
     .first()
-    .getAttribute('value');
-
-  await page.selectOption('select[name="author"]', { label: 'Playwright Test' });
+    .click();
+  const authorId = await page.locator('input[name="author"]').inputValue();
 
   await page.fill('textarea[name="description"]', description);
   await page.click('text=Save');
 
-  // Locate the new row in the table
-  const row = page.locator('tr', { hasText: name });
+  // The list is paged, so the new crate need not be on its first page; open
+  // its detail page through the global search instead.
+  await expect(page).toHaveURL(/\/crates(\?.*)?$/);
+  await page.fill('input[name="search"]', name);
+  await page.locator('li[role="option"]', { hasText: name }).first().click();
+  await expect(page).toHaveURL(/\/crates\/\d+$/);
+
+  // Verify it shows all expected info
+  const details = page.locator('dl');
+  await expect(page.getByRole('heading', { name })).toBeVisible();
+  await expect(details).toContainText(code);
+  await expect(details).toContainText(version);
+  await expect(details).toContainText('Playwright Test'); // Author name
+  await expect(details.locator(`a[href="/rustaceans/${authorId}"]`)).toBeVisible();
+
+  // Bonus: Print the detail page's URL to terminal for CI/debug trace
+  console.log('✅ Created crate:', page.url());
+});
+
+test('shows backend field errors under the matching field', async ({ page }) => {
+  await loginAsAdmin(page);
+
+  await page.click('text=Crates');
+  await page.click('text=Add new crate');
+
+  // Reject the create the way the backend rejects an unknown author
+  await page.route('**/crates', async (route) => {
+    if (route.request().method() !== 'POST') {
+      return route.fallback();
+    }
+    await route.fulfill({
+      status: 422,
+      contentType: 'application/json',
+      body: JSON.stringify({ errors: { rustacean_id: ['Author does not exist'] } }),
+    });
+  });
 
-  // Verify it contains all expected info
-  await expect(row).toContainText(code);
-  await expect(row).toContainText(name);
-  console.log('Row contents:', await row.innerText());
-  // TODO: Uncomment once the version is displayed in the table
-  //await expect(row).toContainText(version);
-  await expect(row).toContainText(authorId!); // Rustacean ID
+  await page.fill('input[name="code"]', `${Math.floor(Math.random() * 1000000)}`);
+  await page.fill('input[name="name"]', `crate-${Date.now()}`);
+  await page.fill('input[name="version"]', '1.0.0');
+  await page.fill('input[name="author-search"]', 'Playwright Test');
+  await page
+    .locator('li[role="option"]', { hasText: 'Playwright Test' })
+    .first()
+    .click();
+  await page.click('text=Save');
 
-  // Bonus: Print row contents to terminal for CI/debug trace
-  const text = await row.innerText();
-  console.log('✅ Created crate row:', text);
+  // The message sits under Author, and the form stays open without an alert
+  const author = page.locator('.author-picker');
+  await expect(author.locator('.invalid-feedback')).toHaveText('Author does not exist');
+  await expect(author.locator('input[name="author-search"]')).toHaveClass(/is-invalid/);
+  await expect(page.locator('.alert-danger')).toHaveCount(0);
+  await expect(page).toHaveURL(/\/crates\/add$/);
 });
@@ -26,9 +26,14 @@ test('can add a rustacean', async ({ page }) => {
             await page.fill('input[name="email"]', email);
             await page.click('text=Save');
 
-            const row = page.locator('tr', { hasText: email });
-            await expect(row).toContainText(name);
-            await expect(row).toContainText(email);
+            // The list is paged, so open the new rustacean through the global
+            // search rather than expecting it on the first page.
+            await expect(page).toHaveURL(/\/rustaceans(\?.*)?$/);
+            await page.fill('input[name="search"]', email);
+            await page.locator('li[role="option"]', { hasText: email }).first().click();
+            await expect(page).toHaveURL(/\/rustaceans\/\d+$/);
+            await expect(page.getByRole('heading', { name })).toBeVisible();
+            await expect(page.locator(`a[href="mailto:${email}"]`)).toBeVisible();
         } catch (e) {
             await page.screenshot({ path: 'rustaceans-failure.png', fullPage: true });
             console.error(await page.content());